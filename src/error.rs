@@ -0,0 +1,21 @@
+use std::fmt::{self, Formatter};
+use std::error::Error;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PfdsError {
+    // The operation needs at least one element.
+    Empty,
+    // The index is negative or not smaller than the length.
+    IndexOutOfRange(i32),
+}
+
+impl fmt::Display for PfdsError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            &PfdsError::Empty => write!(f, "the structure is empty"),
+            &PfdsError::IndexOutOfRange(index) => write!(f, "index {} is out of range", index),
+        }
+    }
+}
+
+impl Error for PfdsError {}
@@ -4,7 +4,7 @@ use list::{List};
 use list::stack::Stack;
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct BinominalTree<T: Clone + Ord + Debug> {
+struct BinominalTree<T: Clone + Ord + Debug> {
     rank: i32,
     element: T,
     children: Box<Stack<BinominalTree<T>>>,
@@ -36,39 +36,51 @@ impl<T: Clone + Ord + Debug> BinominalTree<T> {
     }
 }
 
-
-pub type BinominalHeap<T: Clone + Ord + Debug> = Stack<(i32, BinominalTree<T>)>;
+// The trees ranked in increasing order, behind a private field; build heaps through `Heap`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BinominalHeap<T: Clone + Ord + Debug>(Stack<(i32, BinominalTree<T>)>);
 
 impl<T: Clone + Ord + Debug> BinominalHeap<T> {
     fn from_list(xs: Stack<BinominalTree<T>>, rank: i32) -> Self {
         let result = xs.foldl((rank - 1, Stack::Nil),
                               &|(r, acc), tree| (r - 1, acc.cons((r, tree.clone()))));
-        result.1
+        BinominalHeap(result.1)
     }
 
     fn from_tree(x: BinominalTree<T>, rank: i32) -> Self {
-        Stack::new((rank, x))
+        BinominalHeap(Stack::new((rank, x)))
     }
 
-    fn from_element(x: T) -> Self {
+    pub fn from_element(x: T) -> Self {
         BinominalHeap::from_tree(BinominalTree::new(x), 1)
     }
 
     fn rank(&self) -> i32 {
-        match self {
-            &Stack::Nil => 0,
-            &Stack::Cell((rank, _), _) => rank,
+        match self.0 {
+            Stack::Nil => 0,
+            Stack::Cell((rank, _), _) => rank,
         }
     }
 
+    fn tail(&self) -> Self {
+        match self.0 {
+            Stack::Nil => self.clone(),
+            Stack::Cell(_, ref tail) => BinominalHeap((**tail).clone()),
+        }
+    }
+
+    fn cons(&self, rank: i32, x: BinominalTree<T>) -> Self {
+        BinominalHeap(self.0.cons((rank, x)))
+    }
+
     fn insert_tree_impl(&self, x: BinominalTree<T>, rank_of_tree: i32) -> Self {
-        match self {
-            &Stack::Nil => self.cons((rank_of_tree + 1, x)),
-            &Stack::Cell((ref rank, ref head), box ref tail) => {
+        match self.0 {
+            Stack::Nil => self.cons(rank_of_tree + 1, x),
+            Stack::Cell((ref rank, ref head), _) => {
                 if rank_of_tree < *rank - 1 {
-                    self.cons((rank_of_tree + 1, x))
+                    self.cons(rank_of_tree + 1, x)
                 } else {
-                    tail.clone().insert_tree_impl(x.link(head), *rank)
+                    self.tail().insert_tree_impl(x.link(head), *rank)
                 }
             }
         }
@@ -79,17 +91,17 @@ impl<T: Clone + Ord + Debug> BinominalHeap<T> {
     }
 
     fn remove_min_tree(&self) -> (BinominalTree<T>, Self, i32) {
-        match self {
-            &Stack::Nil => unreachable!(),
-            &Stack::Cell((ref rank, ref head), box ref tail) => {
+        match self.0 {
+            Stack::Nil => unreachable!(),
+            Stack::Cell((ref rank, ref head), ref tail) => {
                 if tail.is_empty() {
-                    (head.clone(), tail.clone(), *rank)
+                    (head.clone(), self.tail(), *rank)
                 } else {
-                    let (o_head, o_tail, o_rank) = tail.remove_min_tree();
+                    let (o_head, o_tail, o_rank) = self.tail().remove_min_tree();
                     if head.clone().element <= o_head.element {
-                        (head.clone(), tail.clone(), *rank)
+                        (head.clone(), self.tail(), *rank)
                     } else {
-                        (o_head.clone(), o_tail.cons((*rank, head.clone())), o_rank)
+                        (o_head.clone(), o_tail.cons(*rank, head.clone()), o_rank)
                     }
                 }
             }
@@ -97,13 +109,13 @@ impl<T: Clone + Ord + Debug> BinominalHeap<T> {
     }
 
     fn find_min_impl(&self, min: &T) -> T {
-        match self {
-            &Stack::Nil => min.clone(),
-            &Stack::Cell((_, ref head), box ref tail) => {
+        match self.0 {
+            Stack::Nil => min.clone(),
+            Stack::Cell((_, ref head), _) => {
                 if &head.element < min {
-                    tail.find_min_impl(&head.element)
+                    self.tail().find_min_impl(&head.element)
                 } else {
-                    tail.find_min_impl(min)
+                    self.tail().find_min_impl(min)
                 }
             } 
         }
@@ -112,11 +124,11 @@ impl<T: Clone + Ord + Debug> BinominalHeap<T> {
 
 impl<T: Clone + Ord + Debug> Heap<T> for BinominalHeap<T> {
     fn empty() -> Self {
-        Stack::Nil
+        BinominalHeap(Stack::Nil)
     }
 
     fn is_empty_heap(&self) -> bool {
-        self.is_empty()
+        self.0.is_empty()
     }
 
     fn insert(&self, x: T) -> Self {
@@ -124,30 +136,29 @@ impl<T: Clone + Ord + Debug> Heap<T> for BinominalHeap<T> {
     }
 
     fn merge(&self, other: &Self) -> Self {
-        match (self.clone(), other.clone()) {
-            (_, Stack::Nil) => self.clone(),
-            (Stack::Nil, _) => other.clone(),
-            (Stack::Cell((ref s_rank, ref s), box ref s_tail),
-             Stack::Cell((ref o_rank, ref o), box ref o_tail)) => {
+        match (&self.0, &other.0) {
+            (_, &Stack::Nil) => self.clone(),
+            (&Stack::Nil, _) => other.clone(),
+            (&Stack::Cell((ref s_rank, ref s), _), &Stack::Cell((ref o_rank, ref o), _)) => {
                 match s_rank {
-                    _ if s_rank < o_rank => s_tail.merge(other).cons((*s_rank, s.clone())),
-                    _ if s_rank > o_rank => o_tail.merge(self).cons((*o_rank, o.clone())),
-                    _ => s_tail.merge(&o_tail).insert_tree_impl(s.link(&o), *s_rank),
+                    _ if s_rank < o_rank => self.tail().merge(other).cons(*s_rank, s.clone()),
+                    _ if s_rank > o_rank => other.tail().merge(self).cons(*o_rank, o.clone()),
+                    _ => self.tail().merge(&other.tail()).insert_tree_impl(s.link(&o), *s_rank),
                 }
             }
         }
     }
 
     fn find_min(&self) -> Option<T> {
-        match self {
-            &Stack::Nil => None,
-            &Stack::Cell((_, ref head), box ref tail) => Some(tail.find_min_impl(&head.element)),
+        match self.0 {
+            Stack::Nil => None,
+            Stack::Cell((_, ref head), _) => Some(self.tail().find_min_impl(&head.element)),
         }
     }
 
     fn delete_min(&self) -> Self {
-        match self {
-            &Stack::Nil => self.clone(),
+        match self.0 {
+            Stack::Nil => self.clone(),
             _ => {
                 let (head, tail, rank) = self.remove_min_tree();
                 let children = *head.children;
@@ -168,7 +179,7 @@ mod tests {
     }
 
     fn is_ordered_heap<T: Clone + Ord + Debug>(heap: &BinominalHeap<T>, min: &T) -> bool {
-        heap.all(&|&(_, ref x)| is_ordered_tree(x, min))
+        heap.0.all(&|&(_, ref x)| is_ordered_tree(x, min))
     }
 
     fn size_from_element<T: Clone + Ord + Debug>(x: &BinominalTree<T>) -> i32 {
@@ -179,12 +190,12 @@ mod tests {
     }
 
     fn size_from_elements<T: Clone + Ord + Debug>(xs: &BinominalHeap<T>) -> i32 {
-        xs.foldl(0, &|acc, &(_, ref x)| acc + size_from_element(x))
+        xs.0.foldl(0, &|acc, &(_, ref x)| acc + size_from_element(x))
     }
 
     // ランクrの二項木は2のr乗のノードを含む
     fn size_from_rank<T: Clone + Ord + Debug>(xs: &BinominalHeap<T>) -> i32 {
-        xs.foldl(0, &|acc, &(ref r, _)| acc + (2 as i32).pow((r - 1) as u32))
+        xs.0.foldl(0, &|acc, &(ref r, _)| acc + (2 as i32).pow((r - 1) as u32))
     }
 
     // サイズ6の二項ヒープ -> 110(二進表記) -> ランク1とランク2の二項木の集合になっていること
@@ -3,7 +3,7 @@ use heap::Heap;
 use heap::binominal::BinominalHeap;
 
 #[derive(Debug, PartialEq, Clone)]
-enum ExplicitMin<T: Clone + Ord + Debug, H: Heap<T> + Clone> {
+pub enum ExplicitMin<T: Clone + Ord + Debug, H: Heap<T> + Clone> {
     Nil,
     Node(T, H),
 }
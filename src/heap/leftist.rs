@@ -25,7 +25,7 @@ impl<T: Clone + Ord + Debug> LeftistHeap<T> {
         }
     }
 
-    fn from_list(xs: Vec<T>) -> Self {
+    pub fn from_list(xs: Vec<T>) -> Self {
         LeftistHeap::from_list_impl(xs.into_iter()
                                         .map(|x| Node(1, x, box Leaf, box Leaf))
                                         .collect::<Vec<_>>())
@@ -1,9 +1,9 @@
 use std::fmt::Debug;
 
-mod leftist;
-mod binominal;
-mod explicit_min;
-mod splay;
+pub mod leftist;
+pub mod binominal;
+pub mod explicit_min;
+pub mod splay;
 
 pub trait Heap<T: Clone + Debug + Ord> {
     fn empty() -> Self;
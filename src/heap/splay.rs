@@ -2,7 +2,7 @@ use std::fmt::Debug;
 use heap::Heap;
 
 #[derive(Debug, Clone)]
-enum SplayHeap<T>
+pub enum SplayHeap<T>
     where T: Clone + Ord + PartialEq + Debug
 {
     Empty,
@@ -63,12 +63,12 @@ impl<T> SplayHeap<T>
         }
     }
 
-    fn from_vec(xs: Vec<T>) -> Self {
+    pub fn from_vec(xs: Vec<T>) -> Self {
         xs.into_iter()
             .fold(SplayHeap::empty(), |acc, x| acc.insert(x))
     }
 
-    fn len(&self) -> i32 {
+    pub fn len(&self) -> i32 {
         match self {
             &Empty => 0,
             &Tree(box ref left, _, box ref right) => 1 + left.len() + right.len(),
@@ -0,0 +1,76 @@
+// Cost accounting for the banker's and physicist's methods. With the `cost` feature enabled,
+// every `Susp` on the current thread reports what it does to a thread-local counter, so tests
+// can check the amortized bounds of lazy structures. Without the feature the hooks are empty.
+
+#[cfg(feature = "cost")]
+use std::cell::Cell;
+#[cfg(feature = "cost")]
+use std::ops::Sub;
+
+#[cfg(feature = "cost")]
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Cost {
+    // Suspensions created with a closure still to run; `Susp::from_value` costs nothing.
+    pub created: usize,
+    // Calls to `force`, `try_force` or `Deref`, whether or not they had anything to do.
+    pub forced: usize,
+    // Suspended closures actually run, including the steps of `bind` chains.
+    pub evaluated: usize,
+}
+
+#[cfg(feature = "cost")]
+impl Sub for Cost {
+    type Output = Cost;
+
+    fn sub(self, other: Cost) -> Cost {
+        Cost {
+            created: self.created - other.created,
+            forced: self.forced - other.forced,
+            evaluated: self.evaluated - other.evaluated,
+        }
+    }
+}
+
+#[cfg(feature = "cost")]
+thread_local!(static COST: Cell<Cost> = Cell::new(Cost::default()));
+
+// Everything counted on this thread so far.
+#[cfg(feature = "cost")]
+pub fn current() -> Cost {
+    COST.with(|cost| cost.get())
+}
+
+// Runs `f` and reports what it cost on this thread.
+#[cfg(feature = "cost")]
+pub fn measure<R, F: FnOnce() -> R>(f: F) -> (R, Cost) {
+    let before = current();
+    let result = f();
+    (result, current() - before)
+}
+
+#[cfg(feature = "cost")]
+fn record<F: FnOnce(&mut Cost)>(f: F) {
+    COST.with(|cost| {
+        let mut current = cost.get();
+        f(&mut current);
+        cost.set(current);
+    })
+}
+
+#[inline]
+pub(crate) fn created() {
+    #[cfg(feature = "cost")]
+    record(|cost| cost.created += 1);
+}
+
+#[inline]
+pub(crate) fn forced() {
+    #[cfg(feature = "cost")]
+    record(|cost| cost.forced += 1);
+}
+
+#[inline]
+pub(crate) fn evaluated() {
+    #[cfg(feature = "cost")]
+    record(|cost| cost.evaluated += 1);
+}
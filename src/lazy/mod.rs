@@ -0,0 +1,978 @@
+use std::ops::{Deref, DerefMut};
+use std::fmt::{self, Debug, Formatter};
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::ptr::read;
+use std::mem::{forget, replace, size_of, size_of_val, ManuallyDrop};
+use std::thread;
+use std::error::Error;
+use std::cmp::PartialEq;
+use std::clone::Clone;
+use std::rc::{Rc, Weak};
+
+#[macro_use]
+mod sync;
+
+pub use self::sync::SyncSusp;
+
+pub mod cost;
+
+use self::Thunk::*;
+use self::Step::*;
+
+pub enum Thunk<T: Debug + PartialEq + Clone> {
+    Suspend,
+    Progress,
+    Evaluated(T),
+    Poisoned,
+}
+
+impl<T: Debug + PartialEq + Clone> Debug for Thunk<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            &Suspend => write!(f, "Suspend {{ (not yet...) }}"),
+            &Progress => write!(f, "Progress"),
+            &Evaluated(ref v) => write!(f, "Evaluated {{ {:?} }}", v),
+            &Poisoned => write!(f, "Poisoned"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ForceError {
+    // The suspension was forced again while its own computation was still running.
+    BlackHole,
+    // An earlier force panicked, so the value will never be available.
+    Poisoned,
+}
+
+impl fmt::Display for ForceError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            &ForceError::BlackHole => write!(f, "suspension forced while being evaluated"),
+            &ForceError::Poisoned => write!(f, "suspension poisoned by a panic during evaluation"),
+        }
+    }
+}
+
+impl Error for ForceError {}
+
+// The result of running a suspended computation. `Then` hands back another suspension whose
+// value becomes ours; forcing follows it in a loop instead of recursing on the native stack.
+pub enum Step<'a, T: 'a + Debug + PartialEq + Clone> {
+    Done(T),
+    Then(Susp<'a, T>),
+}
+
+// Runs a suspended closure in place. The closure must not be touched again afterwards,
+// which is why this is unsafe; it lets a `dyn SuspFn` be consumed without boxing it.
+pub trait SuspFn<'a, T: Debug + PartialEq + Clone> {
+    // The suspension that has to be evaluated before `run`, which then reads its value.
+    fn source(&self) -> Option<&(Node<'a> + 'a)> {
+        None
+    }
+
+    unsafe fn run(&mut self) -> Step<'a, T>;
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: FnOnce() -> T> SuspFn<'a, T> for F {
+    unsafe fn run(&mut self) -> Step<'a, T> {
+        Done(read(self)())
+    }
+}
+
+struct Bound<'a, S: 'a + Debug + PartialEq + Clone, G: ?Sized + SuspFn<'a, S>, K> {
+    source: Susp<'a, S, G>,
+    k: K,
+}
+
+impl<'a, S, G, T, K> SuspFn<'a, T> for Bound<'a, S, G, K>
+    where S: 'a + Debug + PartialEq + Clone,
+          G: 'a + ?Sized + SuspFn<'a, S>,
+          T: 'a + Debug + PartialEq + Clone,
+          K: FnOnce(&S) -> Step<'a, T>
+{
+    fn source(&self) -> Option<&(Node<'a> + 'a)> {
+        Some(&self.source)
+    }
+
+    unsafe fn run(&mut self) -> Step<'a, T> {
+        let source = read(&self.source);
+        let step = read(&self.k)(source.evaluated());
+        step
+    }
+}
+
+mod node {
+    use super::ForceError;
+
+    // A suspension with its value type erased, so that a single loop can force a chain of
+    // suspensions whose values have different types.
+    pub trait Node<'a> {
+        // Ok(true) once evaluated and Ok(false) while still suspended.
+        fn ready(&self) -> Result<bool, ForceError>;
+
+        // Marks the suspension in progress and returns the one it has to wait for first.
+        unsafe fn start(&self) -> Option<*const (Node<'a> + 'a)>;
+
+        // Runs the suspension once what it waited for is evaluated, and returns what it has
+        // to wait for next, if anything.
+        unsafe fn resume(&self) -> Option<*const (Node<'a> + 'a)>;
+
+        // Puts a suspension that was left in progress back as it was, or poisons it.
+        unsafe fn abandon(&self, poison: bool);
+    }
+}
+
+pub use self::node::Node;
+
+// The closure lives inline next to its memo, so a suspension costs a single allocation.
+// `suspend` is live until it has been run; `then` holds the suspension a `Then` step handed
+// back until its value has been copied. `knot` is set on the handles `fix` gives out.
+struct SuspCell<'a, T: 'a + Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> {
+    thunk: UnsafeCell<Thunk<T>>,
+    live: Cell<bool>,
+    then: UnsafeCell<Option<Susp<'a, T>>>,
+    knot: Option<Weak<SuspCell<'a, T, SuspFn<'a, T> + 'a>>>,
+    suspend: UnsafeCell<ManuallyDrop<F>>,
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: SuspFn<'a, T>> SuspCell<'a, T, F> {
+    fn new(thunk: Thunk<T>, f: F) -> Self {
+        let live = match thunk {
+            Suspend => true,
+            _ => false,
+        };
+        if live {
+            cost::created();
+        }
+        SuspCell {
+            thunk: UnsafeCell::new(thunk),
+            live: Cell::new(live),
+            then: UnsafeCell::new(None),
+            knot: None,
+            suspend: UnsafeCell::new(ManuallyDrop::new(f)),
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> SuspCell<'a, T, F> {
+    unsafe fn run(&self) -> Step<'a, T> {
+        self.live.set(false);
+        cost::evaluated();
+        (*self.suspend.get()).deref_mut().run()
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Drop for SuspCell<'a, T, F> {
+    fn drop(&mut self) {
+        if self.live.get() {
+            unsafe { ManuallyDrop::drop(&mut *self.suspend.get()) }
+        }
+    }
+}
+
+// The suspensions a force has started but not finished. If it stops early they are put back
+// as they were, so that they can be forced again later, unless it stopped because one of them
+// panicked: their closures are gone then, and they are poisoned.
+struct Pending<'s, 'a: 's> {
+    frames: Vec<*const (Node<'a> + 's)>,
+}
+
+impl<'s, 'a> Drop for Pending<'s, 'a> {
+    fn drop(&mut self) {
+        let poison = thread::panicking();
+        for &frame in self.frames.iter() {
+            unsafe { (*frame).abandon(poison) }
+        }
+    }
+}
+
+// Forces `root` together with everything it waits on, keeping the suspensions still in
+// progress on an explicit stack. Each frame waits on the one pushed after it; the pointers
+// stay valid because every frame is owned by the one below it, and `root` by the caller.
+unsafe fn force_node<'s, 'a: 's>(root: &'s (Node<'a> + 's)) -> Result<(), ForceError> {
+    let mut pending = Pending { frames: vec![root as *const _] };
+    let mut wait = root.start();
+    while let Some(&top) = pending.frames.last() {
+        wait = match wait {
+            Some(next) => {
+                if !(*next).ready()? {
+                    pending.frames.push(next as *const (Node<'a> + 's));
+                    wait = (*next).start();
+                    continue;
+                }
+                (*top).resume()
+            }
+            None => (*top).resume(),
+        };
+        if wait.is_none() {
+            pending.frames.pop();
+        }
+    }
+    Ok(())
+}
+
+// Dropping the last handle to a suspension drops what its closure captured or its value holds,
+// which may be more suspensions, nested arbitrarily deep. While one such drop runs, the ones it
+// sets off are queued instead and released one at a time by the outermost drop.
+struct Release {
+    ptr: *mut (),
+    drop: unsafe fn(*mut ()),
+}
+
+unsafe fn release<X>(ptr: *mut ()) {
+    drop(Box::from_raw(ptr as *mut X));
+}
+
+thread_local!(static RELEASING: RefCell<Option<Vec<Release>>> = RefCell::new(None));
+
+// Ends a round of releases. Anything still queued after a panic is leaked rather than dropped
+// later, when what it borrows may be gone.
+struct Released<'r>(&'r RefCell<Option<Vec<Release>>>);
+
+impl<'r> Drop for Released<'r> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().take();
+    }
+}
+
+#[macro_export]
+macro_rules! susp {
+    ($e:expr) => {
+        $crate::lazy::Susp::new(move || { $e })
+    }
+}
+
+// Every clone of a Susp points at the same cell, so forcing any of them memoizes for all.
+// `Susp<'a, T>` erases the closure type; `Susp<'a, T, F>` keeps it and forces it statically.
+pub struct Susp<'a, T, F: ?Sized = SuspFn<'a, T> + 'a>
+    where T: 'a + Debug + PartialEq + Clone,
+          F: SuspFn<'a, T>
+{
+    delay: ManuallyDrop<Rc<SuspCell<'a, T, F>>>,
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Debug for Susp<'a, T, F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Susp {{ delay: {:?} }}", self.thunk())
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> PartialEq for Susp<'a, T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Clone for Susp<'a, T, F> {
+    fn clone(&self) -> Self {
+        match self.delay.knot {
+            // A knot copies the value it ties into its own memo when forced, so its clones
+            // must not share it: a memo holding the cell itself would never be freed.
+            Some(ref root) => unsafe { Susp::knot(root.clone()).unerase() },
+            None => Susp::from_cell((*self.delay).clone()),
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Drop for Susp<'a, T, F> {
+    fn drop(&mut self) {
+        let delay = unsafe { read(&*self.delay) };
+        if Rc::strong_count(&delay) > 1 {
+            return;
+        }
+        let mut delay = Some(delay);
+        let _ = RELEASING.try_with(|releasing| {
+            let delay = delay.take().unwrap();
+            if let Some(ref mut queue) = *releasing.borrow_mut() {
+                queue.push(Release {
+                               ptr: Box::into_raw(Box::new(delay)) as *mut (),
+                               drop: release::<Rc<SuspCell<'a, T, F>>>,
+                           });
+                return;
+            }
+            *releasing.borrow_mut() = Some(Vec::new());
+            let _released = Released(releasing);
+            drop(delay);
+            loop {
+                let next = releasing.borrow_mut().as_mut().and_then(|queue| queue.pop());
+                match next {
+                    Some(next) => unsafe { (next.drop)(next.ptr) },
+                    None => break,
+                }
+            }
+        });
+        // The queue is already gone while the thread's locals are being destroyed; drop
+        // directly then.
+        drop(delay);
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone> Susp<'a, T> {
+    // The closure is dropped, along with everything it captured, as soon as it has been run.
+    pub fn new<F>(f: F) -> Self where F: 'a + FnOnce() -> T {
+        Susp::from_fn(f).erase()
+    }
+
+    pub fn from_value(x: T) -> Self {
+        Susp::from_cell(Rc::new(SuspCell::new(Evaluated(x), || -> T { unreachable!() })))
+    }
+
+    // Ties a knot: `f` receives a handle to the suspension it is defining, so the value can
+    // refer to itself lazily (e.g. a stream whose tail is built from the stream). Forcing that
+    // handle from inside `f` is a black hole.
+    //
+    // The handle, and every clone of it, holds the suspension only weakly; forcing one copies
+    // the value into the handle's own memo. So a value that keeps the handle does not keep
+    // itself alive, but a handle forced after the suspension is gone panics. Suspensions that
+    // are built over the handle and wait on each other (e.g. the unforced end of a `zip_with`
+    // of a stream with its own tail) still hold each other once forced.
+    pub fn fix<G>(f: G) -> Self where G: 'a + FnOnce(&Self) -> T {
+        let knot: Rc<RefCell<Option<Weak<SuspCell<'a, T, SuspFn<'a, T> + 'a>>>>> =
+            Rc::new(RefCell::new(None));
+        let tie = knot.clone();
+        let this = Susp::new(move || {
+                                 let root = tie.borrow_mut().take().unwrap();
+                                 f(&Susp::knot(root))
+                             });
+        *knot.borrow_mut() = Some(Rc::downgrade(&*this.delay));
+        this
+    }
+
+    fn knot(root: Weak<SuspCell<'a, T, SuspFn<'a, T> + 'a>>) -> Self {
+        let tie = root.clone();
+        let mut knot = Susp::from_value(()).bind(move |_| {
+            Then(Susp::from_cell(tie.upgrade().expect("forced a fixpoint that is already gone")))
+        });
+        Rc::get_mut(&mut *knot.delay).unwrap().knot = Some(root);
+        knot
+    }
+
+    // Only knots are unerased, and they are only ever created erased.
+    unsafe fn unerase<F: ?Sized + SuspFn<'a, T>>(self) -> Susp<'a, T, F> {
+        let delay = read(&*self.delay);
+        forget(self);
+        debug_assert!(size_of_val(&delay) == size_of::<Rc<SuspCell<'a, T, F>>>());
+        let unerased = read(&delay as *const Rc<_> as *const Rc<SuspCell<'a, T, F>>);
+        forget(delay);
+        Susp::from_cell(unerased)
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: 'a + FnOnce() -> T> Susp<'a, T, F> {
+    pub fn from_fn(f: F) -> Self {
+        Susp::from_cell(Rc::new(SuspCell::new(Suspend, f)))
+    }
+
+    pub fn erase(self) -> Susp<'a, T> {
+        let delay = unsafe { read(&*self.delay) };
+        forget(self);
+        let delay: Rc<SuspCell<'a, T, SuspFn<'a, T> + 'a>> = delay;
+        Susp::from_cell(delay)
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Susp<'a, T, F> {
+    fn from_cell(cell: Rc<SuspCell<'a, T, F>>) -> Self {
+        Susp { delay: ManuallyDrop::new(cell) }
+    }
+
+    pub fn thunk(&self) -> &Thunk<T> {
+        unsafe { &*self.delay.thunk.get() }
+    }
+
+    pub fn is_evaluated(&self) -> bool {
+        match self.thunk() {
+            &Evaluated(_) => true,
+            _ => false,
+        }
+    }
+
+    // The value of a suspension already known to be evaluated; not counted as a force.
+    unsafe fn evaluated(&self) -> &T {
+        match self.thunk() {
+            &Evaluated(ref x) => x,
+            _ => unreachable!(),
+        }
+    }
+
+    // Suspends `k` until this suspension has been forced. Chains of binds, and the `Then`
+    // steps they return, are forced iteratively, however deep they are nested.
+    pub fn bind<U, K>(&self, k: K) -> Susp<'a, U>
+        where F: 'a,
+              U: Debug + PartialEq + Clone,
+              K: 'a + FnOnce(&T) -> Step<'a, U>
+    {
+        let bound = Bound {
+            source: self.clone(),
+            k: k,
+        };
+        let cell: Rc<SuspCell<'a, U, SuspFn<'a, U> + 'a>> = Rc::new(SuspCell::new(Suspend, bound));
+        Susp::from_cell(cell)
+    }
+
+    pub fn map<U, G>(&self, f: G) -> Susp<'a, U>
+        where F: 'a,
+              U: Debug + PartialEq + Clone,
+              G: 'a + FnOnce(&T) -> U
+    {
+        self.bind(move |x| Done(f(x)))
+    }
+
+    pub fn and_then<U, G>(&self, f: G) -> Susp<'a, U>
+        where F: 'a,
+              U: Debug + PartialEq + Clone,
+              G: 'a + FnOnce(&T) -> Susp<'a, U>
+    {
+        self.bind(move |x| Then(f(x)))
+    }
+
+    pub fn zip<U, G>(&self, other: &Susp<'a, U, G>) -> Susp<'a, (T, U)>
+        where F: 'a,
+              U: 'a + Debug + PartialEq + Clone,
+              G: 'a + ?Sized + SuspFn<'a, U>
+    {
+        let that = other.clone();
+        self.bind(move |x| {
+                      let x = x.clone();
+                      Then(that.bind(move |y| Done((x, y.clone()))))
+                  })
+    }
+
+    pub fn unwrap(&self) -> T {
+        (**self).clone()
+    }
+
+    // Moves the value out if this is the last handle to an evaluated suspension.
+    pub fn try_unwrap(mut self) -> Result<T, Self> {
+        match self.take_unique() {
+            Some(x) => Ok(x),
+            None => Err(self),
+        }
+    }
+
+    // Like `try_unwrap`, but leaves the emptied handle behind; lets recursive values such as
+    // stream cells unlink themselves in a loop when they are dropped.
+    pub(crate) fn take_unique(&mut self) -> Option<T> {
+        let cell = match Rc::get_mut(&mut *self.delay) {
+            Some(cell) => cell,
+            None => return None,
+        };
+        let thunk = unsafe { &mut *cell.thunk.get() };
+        match replace(thunk, Poisoned) {
+            Evaluated(x) => Some(x),
+            other => {
+                *thunk = other;
+                None
+            }
+        }
+    }
+
+    pub fn force(&self) {
+        if let Err(e) = self.try_force() {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_force(&self) -> Result<&T, ForceError> {
+        cost::forced();
+        if let &Suspend = self.thunk() {
+            unsafe { force_node(self)? };
+        }
+        match self.thunk() {
+            &Evaluated(ref x) => Ok(x),
+            &Progress => Err(ForceError::BlackHole),
+            &Poisoned => Err(ForceError::Poisoned),
+            &Suspend => unreachable!(),
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Node<'a> for Susp<'a, T, F> {
+    fn ready(&self) -> Result<bool, ForceError> {
+        match self.thunk() {
+            &Suspend => Ok(false),
+            &Evaluated(_) => Ok(true),
+            &Progress => Err(ForceError::BlackHole),
+            &Poisoned => Err(ForceError::Poisoned),
+        }
+    }
+
+    unsafe fn start(&self) -> Option<*const (Node<'a> + 'a)> {
+        let cell = &**self.delay;
+        *cell.thunk.get() = Progress;
+        match *cell.then.get() {
+            Some(ref then) => Some(then as &(Node<'a> + 'a) as *const _),
+            None => (*cell.suspend.get()).source().map(|source| source as *const _),
+        }
+    }
+
+    unsafe fn resume(&self) -> Option<*const (Node<'a> + 'a)> {
+        let cell = &**self.delay;
+        let step = match (*cell.then.get()).take() {
+            Some(then) => Done(then.evaluated().clone()),
+            None => cell.run(),
+        };
+        match step {
+            Done(x) => {
+                *cell.thunk.get() = Evaluated(x);
+                None
+            }
+            Then(susp) => {
+                let then = &mut *cell.then.get();
+                *then = Some(susp);
+                then.as_ref().map(|then| then as &(Node<'a> + 'a) as *const _)
+            }
+        }
+    }
+
+    unsafe fn abandon(&self, poison: bool) {
+        let thunk = &mut *self.delay.thunk.get();
+        if let Progress = *thunk {
+            *thunk = if poison { Poisoned } else { Suspend };
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + Clone, F: ?Sized + SuspFn<'a, T>> Deref for Susp<'a, T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.try_force() {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone)]
+    enum Matchable {
+        One,
+        Two
+    }
+
+    #[test]
+    fn test_lazy_match() {
+        use self::Matchable::*;
+
+        let anonymous = susp!({
+            println!("Evaluated only once.");
+            One
+        });
+
+        let actual = match anonymous.thunk() {
+            &Suspend => {
+                println!("Expect not evaluated yet.");
+                anonymous.map(|x| *x != Two)
+            },
+            _ => unreachable!(),
+        };
+        // Expect not evaluate match yet here.
+        assert!(*actual); // Expect evaluate.
+    }
+
+    #[test]
+    fn test_clone() {
+        let actual = susp!(10).clone();
+        assert!(actual != susp!(20));
+        assert!(actual == susp!(10));
+    }
+
+    #[test]
+    fn test_clone_shares_evaluation() {
+        use std::cell::Cell;
+
+        let count = Rc::new(Cell::new(0));
+        let counter = count.clone();
+        let original = susp!({
+            counter.set(counter.get() + 1);
+            10
+        });
+        let clones = vec![original.clone(), original.clone(), original.clone()];
+
+        for x in clones.iter() {
+            assert!(*x.clone() == 10);
+        }
+        assert!(*original == 10);
+        assert!(count.get() == 1);
+    }
+
+    #[test]
+    fn test_clone_after_force() {
+        use std::cell::Cell;
+
+        let count = Rc::new(Cell::new(0));
+        let counter = count.clone();
+        let original = susp!({
+            counter.set(counter.get() + 1);
+            10
+        });
+        let before = original.clone();
+        before.force();
+        let after = original.clone();
+
+        match after.thunk() {
+            &Evaluated(ref x) => assert!(*x == 10),
+            _ => unreachable!(),
+        }
+        assert!(original.unwrap() == 10);
+        assert!(count.get() == 1);
+    }
+
+    #[test]
+    fn test_black_hole() {
+        use std::cell::RefCell;
+
+        let knot: Rc<RefCell<Option<Susp<Result<i32, ForceError>>>>> = Rc::new(RefCell::new(None));
+        let this = knot.clone();
+        let actual = susp!({
+            match *this.borrow() {
+                Some(ref x) => x.try_force().map(|_| 0),
+                None => Ok(0),
+            }
+        });
+        *knot.borrow_mut() = Some(actual.clone());
+
+        assert!(actual.try_force() == Ok(&Err(ForceError::BlackHole)));
+        *knot.borrow_mut() = None;
+    }
+
+    #[test]
+    fn test_black_hole_in_bind() {
+        use std::cell::RefCell;
+
+        // `b` is forced while its source is still being evaluated. That is a black hole for
+        // now, but once the source has finished `b` can be forced as usual.
+        let knot: Rc<RefCell<Option<Susp<i32>>>> = Rc::new(RefCell::new(None));
+        let this = knot.clone();
+        let a = susp!({
+            let b = this.borrow().clone().unwrap();
+            assert!(b.try_force() == Err(ForceError::BlackHole));
+            assert!(b.try_force() == Err(ForceError::BlackHole));
+            1
+        });
+        let b = a.bind(|x| Done(x + 1));
+        *knot.borrow_mut() = Some(b.clone());
+
+        assert!(*a == 1);
+        assert!(b.try_force() == Ok(&2));
+        *knot.borrow_mut() = None;
+    }
+
+    #[test]
+    fn test_poisoned() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let actual: Susp<i32> = susp!(panic!("evaluation failed"));
+        assert!(catch_unwind(AssertUnwindSafe(|| actual.force())).is_err());
+        assert!(actual.try_force() == Err(ForceError::Poisoned));
+        assert!(actual.clone().try_force() == Err(ForceError::Poisoned));
+        assert!(catch_unwind(AssertUnwindSafe(|| *actual)).is_err());
+    }
+
+    #[test]
+    fn test_release_captures() {
+        let captured = Rc::new(vec![1, 2, 3]);
+        let xs = captured.clone();
+        let actual = susp!(xs.len());
+        let copy = actual.clone();
+        assert!(Rc::strong_count(&captured) == 2);
+
+        assert!(*actual == 3);
+        assert!(Rc::strong_count(&captured) == 1);
+        assert!(*copy == 3);
+    }
+
+    #[test]
+    fn test_fn_once() {
+        let xs = vec![1, 2, 3];
+        let actual = susp!(xs.into_iter().sum::<i32>());
+        assert!(*actual == 6);
+    }
+
+    fn double<'a, F: 'a + FnOnce() -> i32>(x: &Susp<'a, i32, F>) -> i32 {
+        **x * 2
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let actual = Susp::from_fn(|| 10);
+        assert!(!actual.is_evaluated());
+        assert!(double(&actual) == 20);
+        assert!(actual.is_evaluated());
+    }
+
+    #[test]
+    fn test_erase() {
+        let y = 2;
+        let typed = Susp::from_fn(move || y);
+        let actual = vec![Susp::from_fn(|| 1).erase(), typed.clone().erase(), susp!(3)];
+
+        assert!(actual.iter().fold(0, |acc, x| acc + **x) == 6);
+        assert!(typed.is_evaluated());
+    }
+
+    #[test]
+    fn test_fix() {
+        let actual = Susp::fix(|this: &Susp<i32>| {
+                                   assert!(!this.is_evaluated());
+                                   42
+                               });
+        assert!(*actual == 42);
+
+        let actual = Susp::fix(|this: &Susp<Option<ForceError>>| this.try_force().err());
+        assert!(*actual == Some(ForceError::BlackHole));
+    }
+
+    #[test]
+    fn test_fix_unforced() {
+        let captured = Rc::new(0);
+        let actual = {
+            let captured = captured.clone();
+            Susp::fix(move |_| *captured)
+        };
+        assert!(Rc::strong_count(&captured) == 2);
+        drop(actual);
+        assert!(Rc::strong_count(&captured) == 1);
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Tied(Rc<()>, Susp<'static, Tied>);
+
+    #[test]
+    fn test_fix_forced() {
+        let captured = Rc::new(());
+        let actual = {
+            let captured = captured.clone();
+            Susp::fix(move |this: &Susp<Tied>| Tied(captured, this.clone()))
+        };
+        let tail = actual.1.clone();
+        assert!(Rc::ptr_eq(&tail.0, &captured));
+        assert!(Rc::ptr_eq(&tail.1 .0, &captured));
+        drop(actual);
+        assert!(Rc::strong_count(&captured) == 3);
+        drop(tail);
+        assert!(Rc::strong_count(&captured) == 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "forced a fixpoint that is already gone")]
+    fn test_fix_gone() {
+        let tail = Susp::fix(|this: &Susp<Tied>| Tied(Rc::new(()), this.clone())).1.clone();
+        tail.force();
+    }
+
+    #[test]
+    fn test_from_value() {
+        let actual = Susp::from_value(10);
+        assert!(actual.is_evaluated());
+        assert!(*actual == 10);
+    }
+
+    #[test]
+    fn test_map() {
+        let x = susp!(10);
+        let actual = x.map(|x| x * 2);
+        assert!(!x.is_evaluated());
+        assert!(*actual == 20);
+        assert!(x.is_evaluated());
+    }
+
+    #[test]
+    fn test_and_then() {
+        let x = susp!(10);
+        let actual = x.and_then(|x| {
+                                    let x = *x;
+                                    susp!(x + 1)
+                                });
+        assert!(!x.is_evaluated());
+        assert!(*actual == 11);
+        assert!(x.is_evaluated());
+    }
+
+    #[test]
+    fn test_zip() {
+        let x = susp!(10);
+        let y = susp!(20);
+        let actual = x.zip(&y).map(|&(x, y)| x + y);
+        assert!(!x.is_evaluated());
+        assert!(!y.is_evaluated());
+        assert!(*actual == 30);
+        assert!(x.is_evaluated() && y.is_evaluated());
+    }
+
+    #[test]
+    fn test_deep_bind() {
+        let mut actual = susp!(0);
+        for _ in 0..1000000 {
+            actual = actual.bind(|x| Done(x + 1));
+        }
+        assert!(*actual == 1000000);
+    }
+
+    #[test]
+    fn test_deep_then() {
+        let unit = Susp::from_value(0);
+        let mut actual = susp!(1);
+        for _ in 0..1000000 {
+            let prev = actual;
+            actual = unit.bind(move |_| Then(prev));
+        }
+        assert!(*actual == 1);
+    }
+
+    #[test]
+    fn test_deep_map() {
+        let mut actual = susp!(0);
+        for _ in 0..1000000 {
+            actual = actual.map(|x| x + 1);
+        }
+        assert!(*actual == 1000000);
+    }
+
+    #[test]
+    fn test_deep_zip() {
+        let one = susp!(1);
+        let mut actual = susp!(0);
+        for _ in 0..1000000 {
+            actual = actual.zip(&one).and_then(|&(x, y)| Susp::from_value(x + y));
+        }
+        assert!(*actual == 1000000);
+    }
+
+    #[test]
+    fn test_drop_deep_then() {
+        let unit = Susp::from_value(0);
+        let mut actual = susp!(1);
+        for _ in 0..1000000 {
+            let prev = actual;
+            actual = unit.bind(move |_| Then(prev));
+        }
+        drop(actual);
+    }
+
+    #[test]
+    fn test_drop_deep_bind() {
+        let mut actual = susp!(0);
+        for _ in 0..1000000 {
+            actual = actual.bind(|x| Done(x + 1));
+        }
+        drop(actual);
+    }
+
+    #[test]
+    fn test_drop_at_thread_exit() {
+        thread_local!(static KEPT: RefCell<Option<Susp<'static, i32>>> = RefCell::new(None));
+
+        let worker = thread::spawn(|| {
+                                       KEPT.with(|kept| *kept.borrow_mut() = Some(susp!(1)));
+                                       drop(susp!(2));
+                                   });
+        assert!(worker.join().is_ok());
+    }
+
+    #[test]
+    fn test_poisoned_bind() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let source: Susp<i32> = susp!(panic!("evaluation failed"));
+        let middle = source.bind(|x| Done(x + 1));
+        let actual = middle.bind(|x| Done(x + 1));
+        assert!(catch_unwind(AssertUnwindSafe(|| actual.force())).is_err());
+        assert!(actual.try_force() == Err(ForceError::Poisoned));
+        assert!(middle.try_force() == Err(ForceError::Poisoned));
+        assert!(source.try_force() == Err(ForceError::Poisoned));
+    }
+
+    #[test]
+    fn test_try_unwrap() {
+        let actual = susp!(10);
+        let copy = actual.clone();
+        let actual = actual.try_unwrap().unwrap_err();
+        drop(copy);
+        let actual = actual.try_unwrap().unwrap_err();
+        actual.force();
+        assert!(actual.try_unwrap() == Ok(10));
+    }
+
+    #[test]
+    fn test_try_force() {
+        assert!(susp!(10).try_force() == Ok(&10));
+    }
+
+    #[test]
+    fn test_ne() {
+        assert!(susp!(10) != susp!(20));
+        assert!(!(susp!(10) != susp!(10)));
+    }
+
+    #[test]
+    fn test_unwrap() {
+        assert!(susp!(10).unwrap() == 10);
+    }
+
+    #[test]
+    fn test_eq() {
+        assert!(susp!(10) == susp!(10));
+        assert!(!(susp!(10) == susp!(20)));
+    }
+
+    fn plus<'a>(x: Susp<'a, i32>, y: Susp<'a, i32>) -> Susp<'a, i32> {
+        susp!({
+            println!("Evaluate only once!");
+            *x + *y
+        })
+    }
+
+    #[test]
+    fn test_lazy() {
+        let actual = plus(susp!(10), susp!(20));
+        println!("Before evaluate");
+
+        assert!(*actual == 30);
+        assert!(*actual == 30);
+        assert!(*actual == 30);
+    }
+
+    #[test]
+    fn test_susp() {
+        let actual = susp!({
+                               println!("Evaluate only once!");
+                                10
+                           });
+        assert!(*actual == 10);
+        assert!(*actual == 10);
+        assert!(*actual == 10);
+    }
+
+    #[test]
+    fn test_thunk() {
+        let actual = Susp::new(move || {
+                                   println!("Evaluate only once!");
+                                   10
+                               });
+        assert!(*actual == 10);
+        assert!(*actual == 10);
+        assert!(*actual == 10);
+    }
+
+    #[test]
+    #[cfg(feature = "cost")]
+    fn test_cost() {
+        let (actual, cost) = cost::measure(|| {
+                                               let x = susp!(1);
+                                               let y = x.map(|x| x + 1);
+                                               (*y, *y, *x)
+                                           });
+        // Forcing `y` evaluates `x` on the way without a force of its own.
+        assert!(actual == (2, 2, 1));
+        assert!(cost == cost::Cost { created: 2, forced: 3, evaluated: 2 });
+
+        let (_, cost) = cost::measure(|| Susp::from_value(1).bind(|x| Done(x + 1)).force());
+        assert!(cost == cost::Cost { created: 1, forced: 1, evaluated: 1 });
+    }
+}
@@ -0,0 +1,220 @@
+use std::ops::Deref;
+use std::fmt::{self, Debug, Formatter};
+use std::cell::UnsafeCell;
+use std::sync::{Arc, Mutex, Once};
+use std::thread::{self, ThreadId};
+use std::mem::replace;
+use super::ForceError;
+
+#[macro_export]
+macro_rules! sync_susp {
+    ($e:expr) => {
+        $crate::lazy::SyncSusp::new(move || { $e })
+    }
+}
+
+struct SyncCell<T> {
+    once: Once,
+    // The thread running `suspend`, so that a re-entrant force fails instead of deadlocking.
+    evaluator: Mutex<Option<ThreadId>>,
+    suspend: UnsafeCell<Option<Box<FnOnce() -> T + Send>>>,
+    // Still empty after `once` has completed if `suspend` panicked.
+    evaluated: UnsafeCell<Option<T>>,
+}
+
+// `suspend` is only touched inside `Once::call_once_force` and `evaluated` is only written
+// there, so sharing the cell is as safe as sharing the value itself.
+unsafe impl<T: Send + Sync> Sync for SyncCell<T> {}
+
+struct Evaluating<'c>(&'c Mutex<Option<ThreadId>>);
+
+impl<'c> Drop for Evaluating<'c> {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+pub struct SyncSusp<T: Debug + PartialEq + Clone> {
+    cell: Arc<SyncCell<T>>,
+}
+
+impl<T: Debug + PartialEq + Clone> SyncSusp<T> {
+    pub fn new<F>(f: F) -> Self
+        where F: 'static + Send + FnOnce() -> T
+    {
+        SyncSusp {
+            cell: Arc::new(SyncCell {
+                               once: Once::new(),
+                               evaluator: Mutex::new(None),
+                               suspend: UnsafeCell::new(Some(box f)),
+                               evaluated: UnsafeCell::new(None),
+                           }),
+        }
+    }
+
+    pub fn from_value(x: T) -> Self {
+        let cell = SyncCell {
+            once: Once::new(),
+            evaluator: Mutex::new(None),
+            suspend: UnsafeCell::new(None),
+            evaluated: UnsafeCell::new(Some(x)),
+        };
+        cell.once.call_once(|| {});
+        SyncSusp { cell: Arc::new(cell) }
+    }
+
+    pub fn unwrap(&self) -> T {
+        (**self).clone()
+    }
+
+    // The value, if this is the only handle to an evaluated cell.
+    pub(crate) fn take_unique(&mut self) -> Option<T> {
+        match Arc::get_mut(&mut self.cell) {
+            Some(cell) if cell.once.is_completed() => replace(cell.evaluated.get_mut(), None),
+            _ => None,
+        }
+    }
+
+    pub fn force(&self) {
+        if let Err(e) = self.try_force() {
+            panic!("{}", e);
+        }
+    }
+
+    // Other threads forcing the same cell wait for its value; a panic in the thunk poisons
+    // the cell for all of them.
+    pub fn try_force(&self) -> Result<&T, ForceError> {
+        let cell = &*self.cell;
+        if !cell.once.is_completed() {
+            if *cell.evaluator.lock().unwrap() == Some(thread::current().id()) {
+                return Err(ForceError::BlackHole);
+            }
+            cell.once.call_once_force(|state| unsafe {
+                if state.is_poisoned() {
+                    return;
+                }
+                *cell.evaluator.lock().unwrap() = Some(thread::current().id());
+                let _evaluating = Evaluating(&cell.evaluator);
+                match (*cell.suspend.get()).take() {
+                    Some(f) => *cell.evaluated.get() = Some(f()),
+                    None => unreachable!(),
+                }
+            });
+        }
+        match unsafe { &*cell.evaluated.get() } {
+            &Some(ref x) => Ok(x),
+            &None => Err(ForceError::Poisoned),
+        }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone> Deref for SyncSusp<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.try_force() {
+            Ok(x) => x,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone> Clone for SyncSusp<T> {
+    fn clone(&self) -> Self {
+        SyncSusp { cell: self.cell.clone() }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone> PartialEq for SyncSusp<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Debug + PartialEq + Clone> Debug for SyncSusp<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if !self.cell.once.is_completed() {
+            write!(f, "Suspend {{ (not yet...) }}")
+        } else if let Ok(x) = self.try_force() {
+            write!(f, "Evaluated {{ {:?} }}", x)
+        } else {
+            write!(f, "Poisoned")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    fn is_send_sync<T: Send + Sync>(_: &T) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_send_sync() {
+        assert!(is_send_sync(&sync_susp!(10)));
+    }
+
+    #[test]
+    fn test_sync_susp() {
+        let actual = sync_susp!(10);
+        assert!(*actual == 10);
+        assert!(actual.unwrap() == 10);
+        assert!(actual == sync_susp!(10));
+        assert!(actual != sync_susp!(20));
+    }
+
+    #[test]
+    fn test_black_hole() {
+        let knot: Arc<Mutex<Option<SyncSusp<Result<i32, ForceError>>>>> =
+            Arc::new(Mutex::new(None));
+        let this = knot.clone();
+        let actual = sync_susp!({
+            let this = this.lock().unwrap().clone().unwrap();
+            this.try_force().map(|x| x.clone().unwrap_or(0) + 1)
+        });
+        *knot.lock().unwrap() = Some(actual.clone());
+
+        assert!(actual.try_force() == Ok(&Err(ForceError::BlackHole)));
+        *knot.lock().unwrap() = None;
+    }
+
+    #[test]
+    fn test_poisoned() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let actual: SyncSusp<i32> = sync_susp!(panic!("evaluation failed"));
+        assert!(catch_unwind(AssertUnwindSafe(|| actual.force())).is_err());
+        assert!(actual.try_force() == Err(ForceError::Poisoned));
+
+        let shared = actual.clone();
+        let worker = thread::spawn(move || shared.try_force().err());
+        assert!(worker.join().unwrap() == Some(ForceError::Poisoned));
+        assert!(catch_unwind(AssertUnwindSafe(|| *actual)).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_once_across_threads() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        let actual = sync_susp!({
+            counter.fetch_add(1, Ordering::SeqCst);
+            10
+        });
+
+        let workers = (0..8)
+            .map(|_| {
+                     let actual = actual.clone();
+                     thread::spawn(move || *actual)
+                 })
+            .collect::<Vec<_>>();
+
+        for worker in workers {
+            assert!(worker.join().unwrap() == 10);
+        }
+        assert!(count.load(Ordering::SeqCst) == 1);
+    }
+}
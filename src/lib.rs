@@ -2,8 +2,10 @@
 #![feature(box_patterns)]
 
 #[macro_use]
-mod lazy;
+pub mod lazy;
 
-mod list;
-mod set;
-mod heap;
+pub mod error;
+pub mod list;
+pub mod set;
+pub mod heap;
+pub mod prelude;
@@ -0,0 +1,161 @@
+use std::fmt::Debug;
+use list::{Queue, Deque};
+use list::stream::Stream;
+use error::PfdsError;
+
+// Okasaki's banker's deque. Neither side may grow longer than `balance` times the other plus
+// one; when one does, both are rebuilt to half the elements each by a lazy `take` on the long
+// side and a `concat` of the `reverse` of the rest onto the short side. Everything is
+// amortized O(1), and, as with the banker's queue, memoization keeps it so under reuse.
+#[derive(Debug, Clone)]
+pub struct BankersDeque<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+    balance: i32,
+    front_len: i32,
+    front: Stream<'a, T>,
+    rear_len: i32,
+    rear: Stream<'a, T>,
+}
+
+const DEFAULT_BALANCE: i32 = 3;
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> BankersDeque<'a, T> {
+    // An empty deque that keeps each side within `balance` times the other; `balance` must
+    // be at least 2.
+    pub fn with_balance(balance: i32) -> Self {
+        assert!(balance >= 2, "balance must be at least 2");
+        BankersDeque {
+            balance: balance,
+            front_len: 0,
+            front: Stream::empty(),
+            rear_len: 0,
+            rear: Stream::empty(),
+        }
+    }
+
+    fn check(&self,
+             front_len: i32,
+             front: Stream<'a, T>,
+             rear_len: i32,
+             rear: Stream<'a, T>)
+             -> Self {
+        let len = front_len + rear_len;
+        let (front_len, front, rear_len, rear) = if front_len > self.balance * rear_len + 1 {
+            let half = len / 2;
+            (half, front.take(half), len - half, rear.concat(&front.drop_nth(half).reverse()))
+        } else if rear_len > self.balance * front_len + 1 {
+            let half = len / 2;
+            (len - half, front.concat(&rear.drop_nth(half).reverse()), half, rear.take(half))
+        } else {
+            (front_len, front, rear_len, rear)
+        };
+        BankersDeque {
+            balance: self.balance,
+            front_len: front_len,
+            front: front,
+            rear_len: rear_len,
+            rear: rear,
+        }
+    }
+
+    pub fn len(&self) -> i32 {
+        self.front_len + self.rear_len
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Queue<T> for BankersDeque<'a, T> {
+    fn empty() -> Self {
+        BankersDeque::with_balance(DEFAULT_BALANCE)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn snoc(&self, x: T) -> Self {
+        self.check(self.front_len,
+                   self.front.clone(),
+                   self.rear_len + 1,
+                   self.rear.cons(&x))
+    }
+
+    // With an empty front the balance condition leaves at most one element, in the rear.
+    fn head(&self) -> Result<T, PfdsError> {
+        self.front.head().or_else(|| self.rear.head()).cloned().ok_or(PfdsError::Empty)
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        match self.front.tail() {
+            Some(front) => {
+                Ok(self.check(self.front_len - 1, front, self.rear_len, self.rear.clone()))
+            }
+            None if self.rear_len > 0 => Ok(BankersDeque::with_balance(self.balance)),
+            None => Err(PfdsError::Empty),
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Deque<T> for BankersDeque<'a, T> {
+    fn cons(&self, x: T) -> Self {
+        self.check(self.front_len + 1,
+                   self.front.cons(&x),
+                   self.rear_len,
+                   self.rear.clone())
+    }
+
+    fn last(&self) -> Result<T, PfdsError> {
+        self.rear.head().or_else(|| self.front.head()).cloned().ok_or(PfdsError::Empty)
+    }
+
+    fn init(&self) -> Result<Self, PfdsError> {
+        match self.rear.tail() {
+            Some(rear) => {
+                Ok(self.check(self.front_len, self.front.clone(), self.rear_len - 1, rear))
+            }
+            None if self.front_len > 0 => Ok(BankersDeque::with_balance(self.balance)),
+            None => Err(PfdsError::Empty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use list::tests::Copied;
+    use std::rc::Rc;
+    use std::cell::Cell;
+
+    #[test]
+    #[should_panic]
+    fn test_with_balance() {
+        BankersDeque::<i32>::with_balance(1);
+    }
+
+    #[test]
+    fn test_copies_under_reuse() {
+        // Drain a deque alternately from both ends, and pop both ends of every version on the
+        // way three times over. Rebalancing a batched deque copies half of it whenever a side
+        // runs empty, again for every reuse; here the copies stay linear in the operations.
+        let n = 2000;
+        let copies = Rc::new(Cell::new(0));
+        let deque = (0..n).fold(BankersDeque::empty(),
+                                |d, x| d.snoc(Copied(x, copies.clone())));
+        copies.set(0);
+
+        let mut rest = deque;
+        let mut ops = 0;
+        while !rest.is_empty() {
+            for _ in 0..3 {
+                rest.tail().unwrap().head().ok();
+                rest.init().unwrap().last().ok();
+                ops += 4;
+            }
+            rest = if rest.len() % 2 == 0 {
+                rest.tail().unwrap()
+            } else {
+                rest.init().unwrap()
+            };
+            ops += 1;
+        }
+        assert!(copies.get() <= ops);
+    }
+}
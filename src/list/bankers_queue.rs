@@ -0,0 +1,109 @@
+use std::fmt::Debug;
+use list::Queue;
+use list::stream::Stream;
+use error::PfdsError;
+
+// Okasaki's banker's queue. The rear is rotated onto the front by a lazy `concat` of its
+// `reverse` as soon as it grows longer than the front; the rotation is paid for by the
+// front elements before it, and memoization keeps that true when old versions are reused.
+#[derive(Debug, Clone)]
+pub struct BankersQueue<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+    front_len: i32,
+    front: Stream<'a, T>,
+    rear_len: i32,
+    rear: Stream<'a, T>,
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> BankersQueue<'a, T> {
+    fn check(front_len: i32, front: Stream<'a, T>, rear_len: i32, rear: Stream<'a, T>) -> Self {
+        if rear_len <= front_len {
+            BankersQueue {
+                front_len: front_len,
+                front: front,
+                rear_len: rear_len,
+                rear: rear,
+            }
+        } else {
+            BankersQueue {
+                front_len: front_len + rear_len,
+                front: front.concat(&rear.reverse()),
+                rear_len: 0,
+                rear: Stream::empty(),
+            }
+        }
+    }
+
+    pub fn len(&self) -> i32 {
+        self.front_len + self.rear_len
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Queue<T> for BankersQueue<'a, T> {
+    fn empty() -> Self {
+        BankersQueue {
+            front_len: 0,
+            front: Stream::empty(),
+            rear_len: 0,
+            rear: Stream::empty(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.front_len == 0
+    }
+
+    fn snoc(&self, x: T) -> Self {
+        BankersQueue::check(self.front_len,
+                            self.front.clone(),
+                            self.rear_len + 1,
+                            self.rear.cons(&x))
+    }
+
+    fn head(&self) -> Result<T, PfdsError> {
+        self.front.head().cloned().ok_or(PfdsError::Empty)
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        match self.front.tail() {
+            Some(front) => {
+                Ok(BankersQueue::check(self.front_len - 1,
+                                       front,
+                                       self.rear_len,
+                                       self.rear.clone()))
+            }
+            None => Err(PfdsError::Empty),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cost"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_under_reuse() {
+        use lazy::cost;
+
+        // The last snoc rotates the rear onto the front, leaving the reversal of its last 512
+        // elements suspended behind the first 511.
+        let n = 1023;
+        let queue = (0..n).fold(BankersQueue::empty(), |q, x| q.snoc(x));
+        let edge = (0..511).fold(queue.clone(), |q, _| q.tail().unwrap());
+
+        // Each of these reuses the same version right before the reversal. A batched queue
+        // would reverse again every time; here the reversal, and the step of the `concat`
+        // that leads into it, are evaluated once and shared.
+        let (_, reused) = cost::measure(|| for _ in 0..n {
+                                            edge.tail().unwrap().head().unwrap();
+                                        });
+        assert!(reused.evaluated <= 2);
+
+        let (_, drained) = cost::measure(|| {
+            let mut rest = queue.clone();
+            while !rest.is_empty() {
+                rest = rest.tail().unwrap();
+            }
+        });
+        assert!(drained.evaluated <= 4 * n as usize);
+    }
+}
@@ -1,146 +1,197 @@
 use std::fmt::Debug;
-use list::{List, is_match_with_vec};
+use list::{List, Queue, Deque};
 use list::linked_list::LinkedList;
+use error::PfdsError;
 
 use self::LinkedList::*;
 
+// The front list in order and the rear list reversed. Whenever one of them runs empty while
+// the other holds two or more elements, the other is split in half between them. The split is
+// O(n) and is redone every time a version is reused; `BankersDeque` does not have that problem,
+// and `RealTimeDeque` never spends more than O(1) on a single operation.
 #[derive(Debug, Clone, PartialEq)]
-struct Deque<T: Clone + PartialOrd + PartialEq + Debug>(LinkedList<T>, LinkedList<T>);
+pub struct BatchedDeque<T: Clone + PartialOrd + PartialEq + Debug>(LinkedList<T>, LinkedList<T>);
 
-impl<T> List<T> for Deque<T>
+impl<T> BatchedDeque<T>
     where T: Clone + PartialEq + PartialOrd + Debug
 {
-    fn empty() -> Self {
-        Deque(LinkedList::empty(), LinkedList::empty())
+    fn check(fore: LinkedList<T>, rear: LinkedList<T>) -> Self {
+        if fore.is_empty() && rear.len() >= 2 {
+            let (r, f) = rear.split();
+            BatchedDeque(f.reverse(), r)
+        } else if rear.is_empty() && fore.len() >= 2 {
+            let (f, r) = fore.split();
+            BatchedDeque(f, r.reverse())
+        } else {
+            BatchedDeque(fore, rear)
+        }
     }
 
-    fn is_empty(&self) -> bool {
+    fn to_list(&self) -> LinkedList<T> {
         match self {
-            &Deque(Nil, _) => true,
-            _ => false,
+            &BatchedDeque(ref fore, ref rear) => fore.concat(rear.reverse()),
         }
     }
 
-    fn cons(&self, x: T) -> Self {
+    pub fn concat(&self, ys: Self) -> Self {
+        let (f, r) = self.to_list().concat(ys.to_list()).split();
+        BatchedDeque(f, r.reverse())
+    }
+
+    pub fn update(&self, index: i32, x: T) -> Result<Self, PfdsError> {
         match self {
-            &Deque(Nil, Nil) => Deque(Nil, Cons(x, box Nil)),
-            &Deque(Nil, ref rear) => {
-                let (f, r) = rear.reverse().split();
-                Deque(f.cons(x), r)
-            }
-            &Deque(ref fore, Nil) => {
-                let (f, r) = fore.split();
-                Deque(f.cons(x), r)
+            &BatchedDeque(ref fore, ref rear) => {
+                let (f_len, r_len) = (fore.len(), rear.len());
+                if index < 0 || index >= f_len + r_len {
+                    Err(PfdsError::IndexOutOfRange(index))
+                } else if index < f_len {
+                    Ok(BatchedDeque(fore.update(index, x)?, rear.clone()))
+                } else {
+                    Ok(BatchedDeque(fore.clone(), rear.update(f_len + r_len - 1 - index, x)?))
+                }
             }
-            &Deque(ref fore, ref rear) => Deque(fore.cons(x), rear.clone()),
         }
     }
+}
+
+impl<T> Queue<T> for BatchedDeque<T>
+    where T: Clone + PartialEq + PartialOrd + Debug
+{
+    fn empty() -> Self {
+        BatchedDeque(LinkedList::empty(), LinkedList::empty())
+    }
 
-    fn head(&self) -> T {
+    fn is_empty(&self) -> bool {
         match self {
-            &Deque(Nil, Nil) => unreachable!(),
-            &Deque(Nil, ref rear) => rear.reverse().head(),
-            &Deque(ref fore, _) => fore.head(),
+            &BatchedDeque(Nil, Nil) => true,
+            _ => false,
         }
     }
 
-    fn tail(&self) -> Self {
+    fn snoc(&self, x: T) -> Self {
         match self {
-            &Deque(Nil, Nil) => self.clone(),
-            &Deque(Nil, ref rear) => {
-                let (f, r) = rear.reverse().split();
-                Deque(f.tail(), r.reverse())
-            }
-            &Deque(ref fore, Nil) => {
-                let (f, r) = fore.split();
-                Deque(f.tail(), r.reverse())
-            }
-            &Deque(Cons(_, box ref tail), ref rear) => Deque(tail.clone(), rear.clone()),
+            &BatchedDeque(ref fore, ref rear) => BatchedDeque::check(fore.clone(), rear.cons(x)),
         }
     }
 
-    fn concat(&self, _: Self) -> Self {
-        unimplemented!();
+    fn head(&self) -> Result<T, PfdsError> {
+        match self {
+            &BatchedDeque(Nil, ref rear) => rear.reverse().head(),
+            &BatchedDeque(ref fore, _) => fore.head(),
+        }
     }
-    fn update(&self, _: i32, _: T) -> Self {
-        unimplemented!();
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        match self {
+            &BatchedDeque(Nil, Nil) => Err(PfdsError::Empty),
+            &BatchedDeque(Nil, ref rear) => Ok(BatchedDeque::check(Nil, rear.init())),
+            &BatchedDeque(ref fore, ref rear) => {
+                Ok(BatchedDeque::check(fore.tail()?, rear.clone()))
+            }
+        }
     }
 }
-impl<T> Deque<T>
+
+impl<T> Deque<T> for BatchedDeque<T>
     where T: Clone + PartialEq + PartialOrd + Debug
 {
-    fn snoc(&self, x: T) -> Self {
+    fn cons(&self, x: T) -> Self {
         match self {
-            &Deque(Nil, Nil) => Deque(Cons(x, box Nil), Nil),
-            &Deque(Nil, ref rear) => {
-                let (f, r) = rear.reverse().split();
-                Deque(f, r.cons(x))
-            }
-            &Deque(ref fore, Nil) => {
-                let (f, r) = fore.split();
-                Deque(f, r.cons(x))
-            }
-            &Deque(ref fore, ref rear) => Deque(fore.clone(), rear.cons(x)),
+            &BatchedDeque(ref fore, ref rear) => BatchedDeque::check(fore.cons(x), rear.clone()),
         }
     }
 
-    fn last(&self) -> T {
+    fn last(&self) -> Result<T, PfdsError> {
         match self {
-            &Deque(Nil, Nil) => unreachable!(),
-            &Deque(ref fore, Nil) => fore.reverse().head(),
-            &Deque(_, ref rear) => rear.head(),
+            &BatchedDeque(ref fore, Nil) => fore.reverse().head(),
+            &BatchedDeque(_, ref rear) => rear.head(),
         }
     }
 
-    fn init(&self) -> Self {
+    fn init(&self) -> Result<Self, PfdsError> {
         match self {
-            &Deque(Nil, Nil) => self.clone(),
-            &Deque(Nil, ref rear) => {
-                let (f, r) = rear.reverse().split();
-                Deque(f, r.reverse().init())
-            }
-            &Deque(ref fore, Nil) => {
-                let (f, r) = fore.split();
-                Deque(f, r.reverse().init())
+            &BatchedDeque(Nil, Nil) => Err(PfdsError::Empty),
+            &BatchedDeque(ref fore, Nil) => Ok(BatchedDeque::check(fore.init(), Nil)),
+            &BatchedDeque(ref fore, ref rear) => {
+                Ok(BatchedDeque::check(fore.clone(), rear.tail()?))
             }
-            &Deque(ref fore, Cons(_, box ref tail)) => Deque(fore.clone(), tail.clone()),
         }
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
+    use list::is_queue_match_with_vec;
 
     #[test]
     fn test_last() {
-        let actual = Deque::empty().snoc(1).snoc(2).snoc(3);
-        assert!(actual.last() == 3);
+        let actual = BatchedDeque::empty().snoc(1).snoc(2).snoc(3);
+        assert!(actual.last() == Ok(3));
+        assert!(BatchedDeque::empty().cons(1).cons(2).last() == Ok(1));
+        assert!(BatchedDeque::<i32>::empty().last() == Err(PfdsError::Empty));
     }
 
     #[test]
     fn test_init() {
-        let actual = Deque::empty().snoc(1).snoc(2).snoc(3);
-        assert!(is_match_with_vec(actual.init(), vec![1, 2]));
-        assert!(is_match_with_vec(actual.init().init(), vec![1]));
+        let actual = BatchedDeque::empty().snoc(1).snoc(2).snoc(3);
+        assert!(is_queue_match_with_vec(actual.init().unwrap(), vec![1, 2]));
+        assert!(is_queue_match_with_vec(actual.init().unwrap().init().unwrap(), vec![1]));
+        let actual = BatchedDeque::empty().cons(3).cons(2).cons(1);
+        assert!(is_queue_match_with_vec(actual.init().unwrap(), vec![1, 2]));
+        assert!(BatchedDeque::<i32>::empty().init() == Err(PfdsError::Empty));
     }
 
     #[test]
     fn test_tail() {
-        let actual = Deque::empty().snoc(1).snoc(2).snoc(3);
-        assert!(is_match_with_vec(actual.tail(), vec![2, 3]));
-        assert!(is_match_with_vec(actual.tail().tail(), vec![3]));
+        let actual = BatchedDeque::empty().snoc(1).snoc(2).snoc(3);
+        assert!(is_queue_match_with_vec(actual.tail().unwrap(), vec![2, 3]));
+        assert!(is_queue_match_with_vec(actual.tail().unwrap().tail().unwrap(), vec![3]));
+        assert!(BatchedDeque::<i32>::empty().tail() == Err(PfdsError::Empty));
+    }
+
+    #[test]
+    fn test_head() {
+        let actual = BatchedDeque::empty().snoc(1).snoc(2).snoc(3);
+        assert!(actual.head() == Ok(1));
+        assert!(BatchedDeque::empty().snoc(1).head() == Ok(1));
+        assert!(BatchedDeque::<i32>::empty().head() == Err(PfdsError::Empty));
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(BatchedDeque::<i32>::empty().is_empty());
+        assert!(!BatchedDeque::empty().cons(1).is_empty());
+        assert!(!BatchedDeque::empty().snoc(1).is_empty());
+        assert!(BatchedDeque::empty().cons(1).init().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_concat() {
+        let actual_1 = BatchedDeque::empty().snoc(2).snoc(3).cons(1);
+        let actual_2 = BatchedDeque::empty().cons(5).cons(4).snoc(6);
+        let actual = actual_1.concat(actual_2);
+        assert!(is_queue_match_with_vec(actual.clone(), vec![1, 2, 3, 4, 5, 6]));
+        assert!(actual.last() == Ok(6));
+    }
+
+    #[test]
+    fn test_update() {
+        let actual = BatchedDeque::empty().snoc(2).snoc(3).cons(1).snoc(4);
+        assert!(is_queue_match_with_vec(actual.update(0, 9).unwrap(), vec![9, 2, 3, 4]));
+        assert!(is_queue_match_with_vec(actual.update(3, 9).unwrap(), vec![1, 2, 3, 9]));
+        assert!(actual.update(-1, 9) == Err(PfdsError::IndexOutOfRange(-1)));
     }
 
     #[test]
     fn test_snoc() {
-        let actual = Deque::empty().snoc(1).snoc(2).snoc(3);
-        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+        let actual = BatchedDeque::empty().snoc(1).snoc(2).snoc(3);
+        assert!(is_queue_match_with_vec(actual, vec![1, 2, 3]));
     }
 
     #[test]
     fn test_cons() {
-        let actual = Deque::empty().cons(3).cons(2).cons(1);
-        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+        let actual = BatchedDeque::empty().cons(3).cons(2).cons(1);
+        assert!(is_queue_match_with_vec(actual, vec![1, 2, 3]));
     }
 }
-
@@ -0,0 +1,221 @@
+use std::fmt::Debug;
+use std::mem::replace;
+use std::sync::Arc;
+use list::{Queue, unlink};
+use error::PfdsError;
+
+use self::Spine::*;
+use self::RotationState::*;
+
+// A list like `LinkedList`, but sharing its cells through `Arc`, so that the queue is `Send`
+// and `Sync` whenever its elements are.
+#[derive(Debug, Clone)]
+enum Spine<T: Debug + Clone> {
+    Nil,
+    Cons(T, Arc<Spine<T>>),
+}
+
+impl<T: Debug + Clone> Drop for Spine<T> {
+    fn drop(&mut self) {
+        unlink(self, |cell| match *cell {
+                   Nil => None,
+                   Cons(_, ref mut tail) => Arc::get_mut(tail).map(|cell| replace(cell, Nil)),
+               });
+    }
+}
+
+impl<T: Debug + Clone> Spine<T> {
+    fn cons(&self, x: T) -> Self {
+        Cons(x, Arc::new(self.clone()))
+    }
+
+    fn head(&self) -> Result<T, PfdsError> {
+        match self {
+            &Nil => Err(PfdsError::Empty),
+            &Cons(ref head, _) => Ok(head.clone()),
+        }
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        match self {
+            &Nil => Err(PfdsError::Empty),
+            &Cons(_, ref tail) => Ok((**tail).clone()),
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> i32 {
+        let mut l = 0;
+        let mut rest = self;
+        while let &Cons(_, ref tail) = rest {
+            l += 1;
+            rest = &**tail;
+        }
+        l
+    }
+}
+
+// The rotation of `front ++ reverse(rear)`, run two steps per operation: `Reversing` reverses
+// the front and the rear side by side, `Appending` moves the reversed front back onto the
+// reversed rear. `ok` counts the reversed front elements still live; `tail` drops one.
+#[derive(Debug, Clone)]
+enum RotationState<T: Debug + PartialEq + PartialOrd + Clone> {
+    Idle,
+    Reversing(i32, Spine<T>, Spine<T>, Spine<T>, Spine<T>),
+    Appending(i32, Spine<T>, Spine<T>),
+    Done(Spine<T>),
+}
+
+impl<T: Debug + PartialEq + PartialOrd + Clone> RotationState<T> {
+    fn exec(&self) -> Self {
+        match self {
+            &Reversing(ok, Cons(ref x, ref f), ref f_rev, Cons(ref y, ref r), ref r_rev) => {
+                Reversing(ok + 1,
+                          (**f).clone(),
+                          f_rev.cons(x.clone()),
+                          (**r).clone(),
+                          r_rev.cons(y.clone()))
+            }
+            &Reversing(ok, Nil, ref f_rev, Cons(ref y, _), ref r_rev) => {
+                Appending(ok, f_rev.clone(), r_rev.cons(y.clone()))
+            }
+            &Appending(0, _, ref r_rev) => Done(r_rev.clone()),
+            &Appending(ok, Cons(ref x, ref f_rev), ref r_rev) => {
+                Appending(ok - 1, (**f_rev).clone(), r_rev.cons(x.clone()))
+            }
+            _ => self.clone(),
+        }
+    }
+
+    fn invalidate(&self) -> Self {
+        match self {
+            &Reversing(ok, ref f, ref f_rev, ref r, ref r_rev) => {
+                Reversing(ok - 1, f.clone(), f_rev.clone(), r.clone(), r_rev.clone())
+            }
+            &Appending(0, _, Cons(_, ref r_rev)) => Done((**r_rev).clone()),
+            &Appending(ok, ref f_rev, ref r_rev) => {
+                Appending(ok - 1, f_rev.clone(), r_rev.clone())
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+// Hood and Melville's real-time queue. Global rebuilding without laziness: once the rear
+// outgrows the front, a copy of the new front is built by `RotationState` a few steps at a
+// time while the old front keeps serving `head` and `tail`. Without suspensions it can be
+// handed to, and shared between, threads.
+#[derive(Debug, Clone)]
+pub struct HoodMelvilleQueue<T: Debug + PartialEq + PartialOrd + Clone> {
+    front_len: i32,
+    front: Spine<T>,
+    state: RotationState<T>,
+    rear_len: i32,
+    rear: Spine<T>,
+}
+
+impl<T: Debug + PartialEq + PartialOrd + Clone> HoodMelvilleQueue<T> {
+    fn exec2(front_len: i32,
+             front: Spine<T>,
+             state: RotationState<T>,
+             rear_len: i32,
+             rear: Spine<T>)
+             -> Self {
+        let (front, state) = match state.exec().exec() {
+            Done(new_front) => (new_front, Idle),
+            state => (front, state),
+        };
+        HoodMelvilleQueue {
+            front_len: front_len,
+            front: front,
+            state: state,
+            rear_len: rear_len,
+            rear: rear,
+        }
+    }
+
+    fn check(front_len: i32,
+             front: Spine<T>,
+             state: RotationState<T>,
+             rear_len: i32,
+             rear: Spine<T>)
+             -> Self {
+        if rear_len <= front_len {
+            HoodMelvilleQueue::exec2(front_len, front, state, rear_len, rear)
+        } else {
+            let state = Reversing(0, front.clone(), Nil, rear, Nil);
+            HoodMelvilleQueue::exec2(front_len + rear_len, front, state, 0, Nil)
+        }
+    }
+
+    pub fn len(&self) -> i32 {
+        self.front_len + self.rear_len
+    }
+}
+
+impl<T: Debug + PartialEq + PartialOrd + Clone> Queue<T> for HoodMelvilleQueue<T> {
+    fn empty() -> Self {
+        HoodMelvilleQueue {
+            front_len: 0,
+            front: Nil,
+            state: Idle,
+            rear_len: 0,
+            rear: Nil,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.front_len == 0
+    }
+
+    fn snoc(&self, x: T) -> Self {
+        HoodMelvilleQueue::check(self.front_len,
+                                 self.front.clone(),
+                                 self.state.clone(),
+                                 self.rear_len + 1,
+                                 self.rear.cons(x))
+    }
+
+    fn head(&self) -> Result<T, PfdsError> {
+        self.front.head()
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        Ok(HoodMelvilleQueue::check(self.front_len - 1,
+                                    self.front.tail()?,
+                                    self.state.invalidate(),
+                                    self.rear_len,
+                                    self.rear.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<Q: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<HoodMelvilleQueue<i32>>();
+    }
+
+    #[test]
+    fn test_incremental_rotation() {
+        // The 63rd snoc starts rotating the 32 rear elements behind the 31 in front; the old
+        // front keeps serving while it runs, and it is finished before that front runs out.
+        let queue = (0..63).fold(HoodMelvilleQueue::empty(), |q, x| q.snoc(x));
+        match queue.state {
+            Reversing(..) => (),
+            _ => panic!("expected a rotation in progress"),
+        }
+        assert!(queue.front.len() == 31);
+
+        let mut rest = queue;
+        for i in 0..63 {
+            assert!(rest.head() == Ok(i));
+            rest = rest.tail().unwrap();
+        }
+        assert!(rest.is_empty());
+    }
+}
@@ -1,14 +1,26 @@
 use std::fmt::Debug;
-use list::{List, is_match_with_vec};
+use std::mem::replace;
+use std::rc::Rc;
+use list::{List, unlink};
+use error::PfdsError;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LinkedList<T: Debug + PartialEq + PartialOrd + Clone> {
     Nil,
-    Cons(T, Box<LinkedList<T>>),
+    Cons(T, Rc<LinkedList<T>>),
 }
 
 use self::LinkedList::*;
 
+impl<T: Debug + PartialEq + PartialOrd + Clone> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        unlink(self, |cell| match *cell {
+                   Nil => None,
+                   Cons(_, ref mut tail) => Rc::get_mut(tail).map(|cell| replace(cell, Nil)),
+               });
+    }
+}
+
 impl<T> List<T> for LinkedList<T>
     where T: Debug + PartialEq + PartialOrd + Clone
 {
@@ -25,42 +37,37 @@ impl<T> List<T> for LinkedList<T>
 
     fn cons(&self, x: T) -> Self {
         match self {
-            &Nil => Cons(x, box Nil),
-            _ => Cons(x, box self.clone()),
+            &Nil => Cons(x, Rc::new(Nil)),
+            _ => Cons(x, Rc::new(self.clone())),
         }
     }
 
-    fn head(&self) -> T {
+    fn head(&self) -> Result<T, PfdsError> {
         match self {
-            &Nil => unreachable!(),
-            &Cons(ref head, _) => head.clone(),
+            &Nil => Err(PfdsError::Empty),
+            &Cons(ref head, _) => Ok(head.clone()),
         }
     }
 
-    fn tail(&self) -> Self {
+    fn tail(&self) -> Result<Self, PfdsError> {
         match self {
-            &Nil => Nil,
-            &Cons(_, box ref tail) => tail.clone(),
+            &Nil => Err(PfdsError::Empty),
+            &Cons(_, ref tail) => Ok((**tail).clone()),
         }
     }
 
     fn concat(&self, ys: Self) -> Self {
-        match self {
-            &Nil => ys,
-            &Cons(ref head, box ref tail) => Cons(head.clone(), box tail.concat(ys)),
-        }
+        self.reverse().reverse_onto(ys)
     }
 
-    fn update(&self, index: i32, x: T) -> Self {
-        match self {
-            &Nil => Nil,
-            &Cons(ref head, ref tail) => {
-                if index == 0 {
-                    Cons(x, tail.clone())
-                } else {
-                    Cons(head.clone(), box tail.update(index - 1, x))
-                }
-            }
+    // Copies the cells before `index`; the ones after it are shared.
+    fn update(&self, index: i32, x: T) -> Result<Self, PfdsError> {
+        if index < 0 {
+            return Err(PfdsError::IndexOutOfRange(index));
+        }
+        match self.split_reversed(index) {
+            (_, &Nil) => Err(PfdsError::IndexOutOfRange(index)),
+            (prefix, &Cons(_, ref tail)) => Ok(prefix.reverse_onto(Cons(x, tail.clone()))),
         }
     }
 }
@@ -69,21 +76,44 @@ impl<T> LinkedList<T>
     where T: Debug + PartialEq + PartialOrd + Clone
 {
     pub fn reverse(&self) -> Self {
-        match self {
-            &Nil => self.clone(),
-            &Cons(ref head, box ref tail) => tail.reverse().snoc(head.clone()),
+        self.reverse_onto(Nil)
+    }
+
+    // reverse(self) ++ acc, one cell at a time.
+    fn reverse_onto(&self, acc: Self) -> Self {
+        let mut reversed = acc;
+        let mut rest = self;
+        while let &Cons(ref head, ref tail) = rest {
+            reversed = Cons(head.clone(), Rc::new(reversed));
+            rest = &**tail;
         }
+        reversed
     }
 
-    fn len_impl(&self, l: i32) -> i32 {
-        match self {
-            &Nil => l,
-            &Cons(_, ref tail) => tail.len_impl(l + 1),
+    // The first `n` cells reversed, and the rest of the list.
+    fn split_reversed(&self, n: i32) -> (Self, &Self) {
+        let mut prefix = Nil;
+        let mut rest = self;
+        for _ in 0..n {
+            match rest {
+                &Nil => break,
+                &Cons(ref head, ref tail) => {
+                    prefix = Cons(head.clone(), Rc::new(prefix));
+                    rest = &**tail;
+                }
+            }
         }
+        (prefix, rest)
     }
 
     pub fn len(&self) -> i32 {
-        self.len_impl(0)
+        let mut l = 0;
+        let mut rest = self;
+        while let &Cons(_, ref tail) = rest {
+            l += 1;
+            rest = &**tail;
+        }
+        l
     }
 
     fn median(&self) -> i32 {
@@ -94,19 +124,18 @@ impl<T> LinkedList<T>
     }
 
     pub fn take(&self, n: i32) -> Self {
-        match self {
-            &Nil => Nil,
-            _ if n == 0 => Nil,
-            &Cons(ref head, ref tail) => Cons(head.clone(), box tail.take(n - 1)),
-        }
+        self.split_reversed(n).0.reverse()
     }
 
     pub fn drop_nth(&self, n: i32) -> Self {
-        match self {
-            &Nil => self.clone(),
-            _ if n == 0 => self.clone(),
-            &Cons(_, box ref tail) => tail.drop_nth(n - 1),
+        let mut rest = self;
+        for _ in 0..n {
+            match rest {
+                &Nil => break,
+                &Cons(_, ref tail) => rest = &**tail,
+            }
         }
+        rest.clone()
     }
 
     pub fn init(&self) -> Self {
@@ -114,10 +143,7 @@ impl<T> LinkedList<T>
     }
 
     pub fn snoc(&self, x: T) -> Self {
-        match self {
-            &Nil => Cons(x, box Nil),
-            &Cons(ref head, ref tail) => Cons(head.clone(), box tail.snoc(x)),
-        }
+        self.concat(Cons(x, Rc::new(Nil)))
     }
 
     pub fn split(&self) -> (Self, Self) {
@@ -131,8 +157,35 @@ impl<T> LinkedList<T>
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
+    use list::is_match_with_vec;
+
+    #[test]
+    fn test_drop_long() {
+        let mut actual = LinkedList::empty();
+        for i in 0..300000 {
+            actual = actual.cons(i);
+        }
+        let shared = actual.tail().unwrap();
+        drop(actual);
+        assert!(shared.head() == Ok(299998));
+    }
+
+    #[test]
+    fn test_long() {
+        let mut actual = LinkedList::empty();
+        for i in 0..300000 {
+            actual = actual.cons(i);
+        }
+        assert!(actual.len() == 300000);
+        assert!(actual.take(299999).len() == 299999);
+        assert!(actual.drop_nth(299999).head() == Ok(0));
+        assert!(actual.snoc(-1).drop_nth(300000).head() == Ok(-1));
+        assert!(actual.update(299999, -1).unwrap().drop_nth(299999).head() == Ok(-1));
+        assert!(actual.split().1.len() == 150000);
+    }
 
     #[test]
     fn test_drop() {
@@ -167,8 +220,19 @@ mod tests {
 
     #[test]
     fn test_update() {
-        let actual = LinkedList::empty().snoc(1).snoc(2).snoc(3).update(1, 4);
-        assert!(is_match_with_vec(actual, vec![1, 4, 3]));
+        let actual = LinkedList::empty().snoc(1).snoc(2).snoc(3);
+        assert!(is_match_with_vec(actual.update(1, 4).unwrap(), vec![1, 4, 3]));
+        assert!(actual.update(3, 4) == Err(PfdsError::IndexOutOfRange(3)));
+    }
+
+    #[test]
+    fn test_head() {
+        let actual = LinkedList::empty().snoc(1).snoc(2);
+        assert!(actual.head() == Ok(1));
+        assert!(actual.uncons() == Ok((1, LinkedList::empty().cons(2))));
+        assert!(actual.get(1) == Ok(2));
+        assert!(LinkedList::<i32>::empty().head() == Err(PfdsError::Empty));
+        assert!(LinkedList::<i32>::empty().tail() == Err(PfdsError::Empty));
     }
 
     #[test]
@@ -185,6 +249,18 @@ mod tests {
         assert!(is_match_with_vec(actual, vec![1, 2, 3]));
     }
 
+    #[test]
+    fn test_sharing() {
+        let xs = LinkedList::empty().cons(1).cons(2);
+        let ys = xs.cons(3);
+        match (&xs, &ys.tail().unwrap()) {
+            (&Cons(_, ref xs_tail), &Cons(_, ref ys_tail)) => {
+                assert!(Rc::ptr_eq(xs_tail, ys_tail))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_snoc() {
         let actual = LinkedList::empty().snoc(1).snoc(2).snoc(3);
@@ -1,34 +1,263 @@
+#[cfg(test)]
 use std::fmt::Debug;
+use error::PfdsError;
 
 pub mod stack;
 pub mod stream;
+pub mod sync_stream;
 pub mod linked_list;
 pub mod queue;
+pub mod bankers_queue;
+pub mod physicists_queue;
+pub mod real_time_queue;
+pub mod hood_melville_queue;
 pub mod deque;
+pub mod bankers_deque;
+pub mod real_time_deque;
 
 pub trait List<T: Clone>: Sized {
     fn empty() -> Self;
     fn is_empty(&self) -> bool;
     fn cons(&self, x: T) -> Self;
-    fn head(&self) -> T;
-    fn tail(&self) -> Self;
+    fn head(&self) -> Result<T, PfdsError>;
+    fn tail(&self) -> Result<Self, PfdsError>;
     fn concat(&self, ys: Self) -> Self;
-    fn update(&self, index: i32, x: T) -> Self;
+    fn update(&self, index: i32, x: T) -> Result<Self, PfdsError>;
+
+    fn uncons(&self) -> Result<(T, Self), PfdsError> {
+        Ok((self.head()?, self.tail()?))
+    }
+
+    fn get(&self, index: i32) -> Result<T, PfdsError> {
+        let out_of_range = |_| PfdsError::IndexOutOfRange(index);
+        if index < 0 {
+            return Err(PfdsError::IndexOutOfRange(index));
+        }
+        let (mut x, mut xs) = self.uncons().map_err(&out_of_range)?;
+        for _ in 0..index {
+            let (next, rest) = xs.uncons().map_err(&out_of_range)?;
+            x = next;
+            xs = rest;
+        }
+        Ok(x)
+    }
+}
+
+// First in, first out: elements are added with `snoc` and come back out of `head`/`tail`.
+pub trait Queue<T: Clone>: Sized {
+    fn empty() -> Self;
+    fn is_empty(&self) -> bool;
+    fn snoc(&self, x: T) -> Self;
+    fn head(&self) -> Result<T, PfdsError>;
+    fn tail(&self) -> Result<Self, PfdsError>;
+}
+
+// A queue that can also be pushed to at the front and popped from at the back.
+pub trait Deque<T: Clone>: Queue<T> {
+    fn cons(&self, x: T) -> Self;
+    fn last(&self) -> Result<T, PfdsError>;
+    fn init(&self) -> Result<Self, PfdsError>;
 }
 
-pub fn is_match_with_vec<T, L>(xs: L, ys: Vec<T>) -> bool
+// Drops a list cell whose tail is either shared or taken out by `next`. Dropping a long
+// uniquely owned spine the default way recurses once per cell and overflows the stack, so the
+// cells are unlinked one at a time instead.
+pub(crate) fn unlink<C, F>(cell: &mut C, next: F)
+    where F: Fn(&mut C) -> Option<C>
+{
+    let mut rest = next(cell);
+    while let Some(mut cell) = rest {
+        rest = next(&mut cell);
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn is_match_with_vec<T, L>(xs: L, ys: Vec<T>) -> bool
     where T: Debug + PartialEq + PartialOrd + Clone,
             L: List<T>
 {
-    if ys.is_empty() {
-        return xs.is_empty()
-    }
-    ys.iter()
-        .fold((xs, true), |(xs, prev), y| {
-            let head = xs.head();
-            let tail = xs.tail();
-            (tail, prev && &head == y)
-        })
-        .1
+    let mut xs = xs;
+    for y in ys.iter() {
+        xs = match xs.uncons() {
+            Ok((ref head, _)) if head != y => return false,
+            Ok((_, tail)) => tail,
+            Err(_) => return false,
+        };
+    }
+    xs.is_empty()
 }
 
+#[cfg(test)]
+pub(crate) fn is_queue_match_with_vec<T, Q>(xs: Q, ys: Vec<T>) -> bool
+    where T: Debug + PartialEq + PartialOrd + Clone,
+          Q: Queue<T>
+{
+    let mut xs = xs;
+    for y in ys.iter() {
+        if xs.head().as_ref() != Ok(y) {
+            return false;
+        }
+        xs = match xs.tail() {
+            Ok(tail) => tail,
+            Err(_) => return false,
+        };
+    }
+    xs.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use list::queue::BatchedQueue;
+    use list::deque::BatchedDeque;
+    use list::bankers_queue::BankersQueue;
+    use list::physicists_queue::PhysicistsQueue;
+    use list::real_time_queue::RealTimeQueue;
+    use list::hood_melville_queue::HoodMelvilleQueue;
+    use list::bankers_deque::BankersDeque;
+    use list::real_time_deque::RealTimeDeque;
+
+    // Counts its clones in a counter shared by all of them, and with that how many elements
+    // a structure copies.
+    #[derive(Debug, PartialEq, PartialOrd)]
+    pub(crate) struct Copied(pub(crate) i32, pub(crate) Rc<Cell<usize>>);
+
+    impl Clone for Copied {
+        fn clone(&self) -> Self {
+            self.1.set(self.1.get() + 1);
+            Copied(self.0, self.1.clone())
+        }
+    }
+
+    // First in, first out, empty errors, and old versions that keep their contents under
+    // further snocs and tails.
+    fn check_queue<Q: Queue<i32>>() {
+        let actual = Q::empty().snoc(1).snoc(2).snoc(3);
+        assert!(is_queue_match_with_vec(actual, vec![1, 2, 3]));
+
+        let actual = Q::empty().snoc(1).snoc(2);
+        assert!(actual.head() == Ok(1));
+        assert!(actual.tail().unwrap().head() == Ok(2));
+        assert!(actual.tail().unwrap().tail().unwrap().is_empty());
+        assert!(Q::empty().head() == Err(PfdsError::Empty));
+        assert!(Q::empty().tail().is_err());
+
+        let actual = (0..10).fold(Q::empty(), |q, x| q.snoc(x));
+        let old = actual.tail().unwrap();
+        let new = old.snoc(10).tail().unwrap();
+        assert!(is_queue_match_with_vec(old, (1..10).collect()));
+        assert!(is_queue_match_with_vec(new, (2..11).collect()));
+        assert!(is_queue_match_with_vec(actual, (0..10).collect()));
+
+        let mut actual = Q::empty();
+        let mut expected = VecDeque::new();
+        for i in 0..1000 {
+            actual = actual.snoc(i);
+            expected.push_back(i);
+            if i % 3 == 0 {
+                actual = actual.tail().unwrap();
+                expected.pop_front();
+            }
+            assert!(actual.head().ok().as_ref() == expected.front());
+        }
+        assert!(is_queue_match_with_vec(actual, expected.into_iter().collect()));
+    }
+
+    #[test]
+    fn test_queues() {
+        check_queue::<BatchedQueue<i32>>();
+        check_queue::<BatchedDeque<i32>>();
+        check_queue::<BankersQueue<i32>>();
+        check_queue::<PhysicistsQueue<i32>>();
+        check_queue::<RealTimeQueue<i32>>();
+        check_queue::<HoodMelvilleQueue<i32>>();
+        check_queue::<BankersDeque<i32>>();
+        check_queue::<RealTimeDeque<i32>>();
+    }
+
+    // Both ends of `empty` against a VecDeque, under a mix of all four operations.
+    fn check_deque<D: Deque<i32>>(empty: D) {
+        let actual = empty.cons(3).cons(2).cons(1);
+        assert!(is_queue_match_with_vec(actual, vec![1, 2, 3]));
+
+        let actual = empty.snoc(1).snoc(2).snoc(3);
+        assert!(actual.head() == Ok(1));
+        assert!(actual.last() == Ok(3));
+        assert!(empty.snoc(1).head() == Ok(1));
+        assert!(empty.cons(1).last() == Ok(1));
+        assert!(empty.head() == Err(PfdsError::Empty));
+        assert!(empty.last() == Err(PfdsError::Empty));
+
+        assert!(is_queue_match_with_vec(actual.tail().unwrap(), vec![2, 3]));
+        assert!(is_queue_match_with_vec(actual.init().unwrap(), vec![1, 2]));
+        assert!(empty.snoc(1).tail().unwrap().is_empty());
+        assert!(empty.cons(1).init().unwrap().is_empty());
+        assert!(empty.tail().is_err());
+        assert!(empty.init().is_err());
+
+        let mut actual = empty;
+        let mut expected = VecDeque::new();
+        for i in 0..2000 {
+            match i % 7 {
+                0 | 3 | 5 => {
+                    actual = actual.cons(i);
+                    expected.push_front(i);
+                }
+                1 | 4 => {
+                    actual = actual.snoc(i);
+                    expected.push_back(i);
+                }
+                2 => {
+                    actual = actual.init().unwrap();
+                    expected.pop_back();
+                }
+                _ => {
+                    actual = actual.tail().unwrap();
+                    expected.pop_front();
+                }
+            }
+            assert!(actual.head().ok().as_ref() == expected.front());
+            assert!(actual.last().ok().as_ref() == expected.back());
+        }
+        assert!(is_queue_match_with_vec(actual, expected.into_iter().collect()));
+    }
+
+    #[test]
+    fn test_deques() {
+        check_deque(BatchedDeque::empty());
+        for &balance in &[2, 3, 5] {
+            check_deque(BankersDeque::with_balance(balance));
+        }
+        check_deque(RealTimeDeque::empty());
+    }
+
+    // The order in which people standing in a circle are eliminated when every k-th one is.
+    fn josephus<Q: Queue<i32>>(n: i32, k: i32) -> Vec<i32> {
+        let mut circle = (1..n + 1).fold(Q::empty(), |q, x| q.snoc(x));
+        let mut eliminated = vec![];
+        while !circle.is_empty() {
+            for _ in 1..k {
+                circle = circle.tail().unwrap().snoc(circle.head().unwrap());
+            }
+            eliminated.push(circle.head().unwrap());
+            circle = circle.tail().unwrap();
+        }
+        eliminated
+    }
+
+    #[test]
+    fn test_generic_over_queues() {
+        let expected = vec![3, 6, 2, 7, 5, 1, 4];
+        assert!(josephus::<BatchedQueue<i32>>(7, 3) == expected);
+        assert!(josephus::<BatchedDeque<i32>>(7, 3) == expected);
+        assert!(josephus::<BankersQueue<i32>>(7, 3) == expected);
+        assert!(josephus::<PhysicistsQueue<i32>>(7, 3) == expected);
+        assert!(josephus::<RealTimeQueue<i32>>(7, 3) == expected);
+        assert!(josephus::<HoodMelvilleQueue<i32>>(7, 3) == expected);
+        assert!(josephus::<BankersDeque<i32>>(7, 3) == expected);
+        assert!(josephus::<RealTimeDeque<i32>>(7, 3) == expected);
+    }
+}
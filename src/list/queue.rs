@@ -1,13 +1,14 @@
 use std::fmt::Debug;
-use list::{List, is_match_with_vec};
+use list::{List, Queue};
 use list::linked_list::LinkedList;
+use error::PfdsError;
 
 use self::LinkedList::*;
 
 #[derive(Debug, Clone, PartialEq)]
-struct BatchedQueue<T: Clone + PartialOrd + PartialEq + Debug>(LinkedList<T>, LinkedList<T>);
+pub struct BatchedQueue<T: Clone + PartialOrd + PartialEq + Debug>(LinkedList<T>, LinkedList<T>);
 
-impl<T> List<T> for BatchedQueue<T>
+impl<T> Queue<T> for BatchedQueue<T>
     where T: Clone + PartialEq + PartialOrd + Debug
 {
     fn empty() -> Self {
@@ -21,47 +22,93 @@ impl<T> List<T> for BatchedQueue<T>
         }
     }
 
-    fn cons(&self, x: T) -> Self {
+    fn snoc(&self, x: T) -> Self {
         match self {
-            &BatchedQueue(Nil, _) => BatchedQueue(Cons(x, box Nil), Nil),
-            &BatchedQueue(ref fore, ref rear) => {
-                BatchedQueue(fore.clone(), Cons(x, box rear.clone()))
-            }
+            &BatchedQueue(Nil, _) => BatchedQueue(Nil.cons(x), Nil),
+            &BatchedQueue(ref fore, ref rear) => BatchedQueue(fore.clone(), rear.cons(x)),
         }
     }
 
-    fn head(&self) -> T {
+    fn head(&self) -> Result<T, PfdsError> {
+        self.0.head()
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
         match self {
-            &BatchedQueue(Nil, _) => unreachable!(),
-            &BatchedQueue(ref fore, _) => fore.head(),
+            &BatchedQueue(Nil, _) => Err(PfdsError::Empty),
+            &BatchedQueue(Cons(_, ref f_tail), ref rear) if f_tail.is_empty() => {
+                Ok(BatchedQueue(rear.reverse(), Nil))
+            }
+            &BatchedQueue(Cons(_, ref f_tail), ref rear) => {
+                Ok(BatchedQueue((**f_tail).clone(), rear.clone()))
+            }
         }
     }
+}
 
-    fn tail(&self) -> Self {
+impl<T> BatchedQueue<T>
+    where T: Clone + PartialEq + PartialOrd + Debug
+{
+    fn to_list(&self) -> LinkedList<T> {
         match self {
-            &BatchedQueue(Nil, ref rear) |
-            &BatchedQueue(Cons(_, box Nil), ref rear) => BatchedQueue(rear.reverse(), Nil),
-            &BatchedQueue(Cons(_, box ref f_tail), ref rear) => {
-                BatchedQueue(f_tail.clone(), rear.clone())
-            }
+            &BatchedQueue(ref fore, ref rear) => fore.concat(rear.reverse()),
         }
     }
 
-    fn concat(&self, _: Self) -> Self {
-        unimplemented!();
+    pub fn concat(&self, ys: Self) -> Self {
+        let xs = self.to_list().concat(ys.to_list());
+        BatchedQueue(xs, Nil)
     }
-    fn update(&self, _: i32, _: T) -> Self {
-        unimplemented!();
+
+    pub fn update(&self, index: i32, x: T) -> Result<Self, PfdsError> {
+        match self {
+            &BatchedQueue(ref fore, ref rear) => {
+                let (f_len, r_len) = (fore.len(), rear.len());
+                if index < 0 || index >= f_len + r_len {
+                    Err(PfdsError::IndexOutOfRange(index))
+                } else if index < f_len {
+                    Ok(BatchedQueue(fore.update(index, x)?, rear.clone()))
+                } else {
+                    Ok(BatchedQueue(fore.clone(), rear.update(f_len + r_len - 1 - index, x)?))
+                }
+            }
+        }
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
+    use list::is_queue_match_with_vec;
 
     #[test]
-    fn test_cons() {
-        let actual = BatchedQueue::empty().cons(1).cons(2).cons(3);
-        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+    fn test_snoc() {
+        let actual = BatchedQueue::empty().snoc(1).snoc(2).snoc(3);
+        assert!(is_queue_match_with_vec(actual, vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_head() {
+        let actual = BatchedQueue::empty().snoc(1).snoc(2);
+        assert!(actual.head() == Ok(1));
+        assert!(actual.tail().unwrap().head() == Ok(2));
+        assert!(BatchedQueue::<i32>::empty().head() == Err(PfdsError::Empty));
+        assert!(BatchedQueue::<i32>::empty().tail() == Err(PfdsError::Empty));
     }
-}
 
+    #[test]
+    fn test_concat() {
+        let actual_1 = BatchedQueue::empty().snoc(1).snoc(2).snoc(3).tail().unwrap().snoc(4);
+        let actual_2 = BatchedQueue::empty().snoc(5).snoc(6);
+        assert!(is_queue_match_with_vec(actual_1.concat(actual_2), vec![2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn test_update() {
+        let actual = BatchedQueue::empty().snoc(1).snoc(2).snoc(3).snoc(4);
+        assert!(is_queue_match_with_vec(actual.update(0, 9).unwrap(), vec![9, 2, 3, 4]));
+        assert!(is_queue_match_with_vec(actual.update(2, 9).unwrap(), vec![1, 2, 9, 4]));
+        assert!(is_queue_match_with_vec(actual.update(3, 9).unwrap(), vec![1, 2, 3, 9]));
+        assert!(actual.update(4, 9) == Err(PfdsError::IndexOutOfRange(4)));
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt::Debug;
+use list::{Queue, Deque};
+use list::stream::{Stream, StreamCell};
+use error::PfdsError;
+
+use self::StreamCell::*;
+
+// The balance constant; the schedules below keep up with rebuilding only for 2 or 3.
+const BALANCE: i32 = 3;
+
+// Drops `n` cells right away. A chain of lazy `drop_nth`s, one per rotation step, would be
+// forced all at once by the first cell that needs the end of it.
+fn skip<'a, T>(s: &Stream<'a, T>, n: i32) -> Stream<'a, T>
+    where T: Debug + PartialEq + PartialOrd + Clone
+{
+    let mut s = s.clone();
+    for _ in 0..n {
+        s = match s.tail() {
+            Some(tail) => tail,
+            None => break,
+        };
+    }
+    s
+}
+
+// f ++ reverse(r) ++ a, moving `BALANCE` elements of `r` per cell of `f`; for
+// |r| <= BALANCE * |f| + BALANCE.
+fn rotate_rev<'a, T>(f: Stream<'a, T>, r: Stream<'a, T>, a: Stream<'a, T>) -> Stream<'a, T>
+    where T: Debug + PartialEq + PartialOrd + Clone
+{
+    Stream::lazy(move || match f.force() {
+                     &Nil => r.reverse().concat(&a).force().clone(),
+                     &Cons(ref x, ref f) => {
+                         let a = r.take(BALANCE).reverse().concat(&a);
+                         Cons(x.clone(), rotate_rev(f.clone(), skip(&r, BALANCE), a))
+                     }
+                 })
+}
+
+// f ++ reverse(drop(j, r)), dropping `BALANCE` elements of `r` per cell of `f` until fewer
+// than that are left to drop.
+fn rotate_drop<'a, T>(f: Stream<'a, T>, j: i32, r: Stream<'a, T>) -> Stream<'a, T>
+    where T: Debug + PartialEq + PartialOrd + Clone
+{
+    if j < BALANCE {
+        return rotate_rev(f, skip(&r, j), Stream::empty());
+    }
+    Stream::lazy(move || match f.force() {
+                     &Nil => skip(&r, j).reverse().force().clone(),
+                     &Cons(ref x, ref f) => {
+                         Cons(x.clone(), rotate_drop(f.clone(), j - BALANCE, skip(&r, BALANCE)))
+                     }
+                 })
+}
+
+// Forces the next scheduled cell, if any.
+fn exec<'a, T>(schedule: &Stream<'a, T>) -> Stream<'a, T>
+    where T: Debug + PartialEq + PartialOrd + Clone
+{
+    schedule.tail().unwrap_or_else(|| schedule.clone())
+}
+
+// Okasaki's real-time deque. It rebalances like `BankersDeque`, but with `rotate_drop` and
+// `rotate_rev` doing the reversal a constant number of elements per cell, and with a schedule
+// on each side that is forced one or two cells per operation so that every rebuilt cell has
+// been evaluated by the time it is reached. Every operation is worst-case O(1).
+#[derive(Debug, Clone)]
+pub struct RealTimeDeque<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+    front_len: i32,
+    front: Stream<'a, T>,
+    front_schedule: Stream<'a, T>,
+    rear_len: i32,
+    rear: Stream<'a, T>,
+    rear_schedule: Stream<'a, T>,
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> RealTimeDeque<'a, T> {
+    fn check(front_len: i32,
+             front: Stream<'a, T>,
+             front_schedule: Stream<'a, T>,
+             rear_len: i32,
+             rear: Stream<'a, T>,
+             rear_schedule: Stream<'a, T>)
+             -> Self {
+        let len = front_len + rear_len;
+        if front_len > BALANCE * rear_len + 1 {
+            let half = len / 2;
+            let rear = rotate_drop(rear, half, front.clone());
+            RealTimeDeque::balanced(half, front.take(half), len - half, rear)
+        } else if rear_len > BALANCE * front_len + 1 {
+            let half = len / 2;
+            let front = rotate_drop(front, half, rear.clone());
+            RealTimeDeque::balanced(len - half, front, half, rear.take(half))
+        } else {
+            RealTimeDeque {
+                front_len: front_len,
+                front: front,
+                front_schedule: front_schedule,
+                rear_len: rear_len,
+                rear: rear,
+                rear_schedule: rear_schedule,
+            }
+        }
+    }
+
+    fn balanced(front_len: i32, front: Stream<'a, T>, rear_len: i32, rear: Stream<'a, T>) -> Self {
+        RealTimeDeque {
+            front_len: front_len,
+            front: front.clone(),
+            front_schedule: front,
+            rear_len: rear_len,
+            rear: rear.clone(),
+            rear_schedule: rear,
+        }
+    }
+
+    pub fn len(&self) -> i32 {
+        self.front_len + self.rear_len
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Queue<T> for RealTimeDeque<'a, T> {
+    fn empty() -> Self {
+        RealTimeDeque::balanced(0, Stream::empty(), 0, Stream::empty())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn snoc(&self, x: T) -> Self {
+        RealTimeDeque::check(self.front_len,
+                             self.front.clone(),
+                             exec(&self.front_schedule),
+                             self.rear_len + 1,
+                             self.rear.cons(&x),
+                             exec(&self.rear_schedule))
+    }
+
+    // The front is empty only if the rear holds at most one element; see `BankersDeque::head`.
+    fn head(&self) -> Result<T, PfdsError> {
+        self.front.head().or_else(|| self.rear.head()).cloned().ok_or(PfdsError::Empty)
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        match self.front.tail() {
+            Some(front) => {
+                Ok(RealTimeDeque::check(self.front_len - 1,
+                                        front,
+                                        exec(&exec(&self.front_schedule)),
+                                        self.rear_len,
+                                        self.rear.clone(),
+                                        exec(&exec(&self.rear_schedule))))
+            }
+            None if self.rear_len > 0 => Ok(RealTimeDeque::empty()),
+            None => Err(PfdsError::Empty),
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Deque<T> for RealTimeDeque<'a, T> {
+    fn cons(&self, x: T) -> Self {
+        RealTimeDeque::check(self.front_len + 1,
+                             self.front.cons(&x),
+                             exec(&self.front_schedule),
+                             self.rear_len,
+                             self.rear.clone(),
+                             exec(&self.rear_schedule))
+    }
+
+    fn last(&self) -> Result<T, PfdsError> {
+        self.rear.head().or_else(|| self.front.head()).cloned().ok_or(PfdsError::Empty)
+    }
+
+    fn init(&self) -> Result<Self, PfdsError> {
+        match self.rear.tail() {
+            Some(rear) => {
+                Ok(RealTimeDeque::check(self.front_len,
+                                        self.front.clone(),
+                                        exec(&exec(&self.front_schedule)),
+                                        self.rear_len - 1,
+                                        rear,
+                                        exec(&exec(&self.rear_schedule))))
+            }
+            None if self.front_len > 0 => Ok(RealTimeDeque::empty()),
+            None => Err(PfdsError::Empty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use list::tests::Copied;
+    use std::rc::Rc;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_copies_per_operation() {
+        // Build a deque from the back, then drain it from the back, so that every rebuild
+        // reverses most of the deque from one side onto the other. No single operation
+        // copies more than a constant number of elements.
+        let n = 5000;
+        let copies = Rc::new(Cell::new(0));
+        let mut deque = RealTimeDeque::empty();
+        let mut worst = 0;
+        for i in 0..n {
+            copies.set(0);
+            deque = deque.snoc(Copied(i, copies.clone()));
+            deque.last().unwrap();
+            worst = worst.max(copies.get());
+        }
+        for i in (0..n).rev() {
+            copies.set(0);
+            assert!(deque.last().unwrap().0 == i);
+            deque = deque.init().unwrap();
+            worst = worst.max(copies.get());
+        }
+        assert!(worst <= 20);
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt::Debug;
+use list::{List, Queue};
+use list::linked_list::LinkedList;
+use list::stream::{Stream, StreamCell};
+use error::PfdsError;
+
+// Okasaki's real-time queue. Instead of reversing the rear in one go, `rotate` moves one rear
+// element per step, and `schedule` points at the first unevaluated cell of the front; every
+// operation forces exactly one cell of it, so no single operation pays for a whole rotation.
+#[derive(Debug, Clone)]
+pub struct RealTimeQueue<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+    front: Stream<'a, T>,
+    rear: LinkedList<T>,
+    schedule: Stream<'a, T>,
+}
+
+// front ++ reverse(rear) ++ acc, one step per cell, for |rear| == |front| + 1.
+fn rotate<'a, T>(front: Stream<'a, T>, rear: LinkedList<T>, acc: Stream<'a, T>) -> Stream<'a, T>
+    where T: Debug + PartialEq + PartialOrd + Clone
+{
+    Stream::lazy(move || match (front.force(), &rear) {
+                     (&StreamCell::Nil, &LinkedList::Cons(ref y, _)) => {
+                         StreamCell::Cons(y.clone(), acc)
+                     }
+                     (&StreamCell::Cons(ref x, ref xs), &LinkedList::Cons(ref y, ref ys)) => {
+                         let rest = rotate(xs.clone(), (**ys).clone(), acc.cons(y));
+                         StreamCell::Cons(x.clone(), rest)
+                     }
+                     (_, &LinkedList::Nil) => front.concat(&acc).force().clone(),
+                 })
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> RealTimeQueue<'a, T> {
+    fn exec(front: Stream<'a, T>, rear: LinkedList<T>, schedule: Stream<'a, T>) -> Self {
+        match schedule.tail() {
+            Some(schedule) => {
+                RealTimeQueue {
+                    front: front,
+                    rear: rear,
+                    schedule: schedule,
+                }
+            }
+            None => {
+                let front = rotate(front, rear, Stream::empty());
+                RealTimeQueue {
+                    front: front.clone(),
+                    rear: LinkedList::Nil,
+                    schedule: front,
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Queue<T> for RealTimeQueue<'a, T> {
+    fn empty() -> Self {
+        RealTimeQueue {
+            front: Stream::empty(),
+            rear: LinkedList::Nil,
+            schedule: Stream::empty(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.front.is_empty()
+    }
+
+    fn snoc(&self, x: T) -> Self {
+        RealTimeQueue::exec(self.front.clone(), self.rear.cons(x), self.schedule.clone())
+    }
+
+    fn head(&self) -> Result<T, PfdsError> {
+        self.front.head().cloned().ok_or(PfdsError::Empty)
+    }
+
+    fn tail(&self) -> Result<Self, PfdsError> {
+        match self.front.tail() {
+            Some(front) => {
+                Ok(RealTimeQueue::exec(front, self.rear.clone(), self.schedule.clone()))
+            }
+            None => Err(PfdsError::Empty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_length() {
+        // The schedule is as long as the front is longer than the rear, so it runs out exactly
+        // when the rear catches up and the next rotation has to start.
+        let mut queue = RealTimeQueue::empty();
+        for i in 0..100 {
+            queue = queue.snoc(i);
+            if i % 3 == 1 {
+                queue = queue.tail().unwrap();
+            }
+            let (front, schedule) = (queue.front.iter().count(), queue.schedule.iter().count());
+            assert!(schedule as i32 == front as i32 - queue.rear.len());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cost")]
+    fn test_cost_per_operation() {
+        use lazy::cost;
+
+        // Every operation, including the ones that start or finish a rotation of thousands of
+        // elements, forces and evaluates a bounded number of suspensions.
+        let mut queue = RealTimeQueue::empty();
+        let mut costs = Vec::new();
+        for i in 0..10000 {
+            let (next, snoc) = cost::measure(|| queue.snoc(i));
+            queue = next;
+            costs.push(snoc);
+            if i % 3 == 1 {
+                let (next, tail) = cost::measure(|| queue.tail().unwrap());
+                queue = next;
+                costs.push(tail);
+            }
+            let (head, cost) = cost::measure(|| queue.head().unwrap());
+            assert!(head == (i + 2) / 3);
+            costs.push(cost);
+        }
+        assert!(costs.iter().all(|cost| cost.forced <= 3 && cost.evaluated <= 1));
+    }
+}
@@ -1,14 +1,26 @@
 use std::fmt::Debug;
-use list::List;
+use std::mem::replace;
+use std::rc::Rc;
+use list::{List, unlink};
+use error::PfdsError;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Stack<T: Clone + Debug> {
     Nil,
-    Cell(T, Box<Stack<T>>),
+    Cell(T, Rc<Stack<T>>),
 }
 
 use self::Stack::*;
 
+impl<T: Clone + Debug> Drop for Stack<T> {
+    fn drop(&mut self) {
+        unlink(self, |cell| match *cell {
+                   Nil => None,
+                   Cell(_, ref mut tail) => Rc::get_mut(tail).map(|cell| replace(cell, Nil)),
+               });
+    }
+}
+
 impl<T: Clone + Debug> List<T> for Stack<T> {
     fn empty() -> Self {
         Nil
@@ -23,39 +35,44 @@ impl<T: Clone + Debug> List<T> for Stack<T> {
 
     fn cons(&self, x: T) -> Self {
         match self {
-            &Nil => Cell(x, box Nil),
-            &Cell(_, _) => Cell(x, box self.clone()),
+            &Nil => Cell(x, Rc::new(Nil)),
+            &Cell(_, _) => Cell(x, Rc::new(self.clone())),
         }
     }
 
-    fn head(&self) -> T {
+    fn head(&self) -> Result<T, PfdsError> {
         match self {
-            &Nil => panic!("List is empty!"),
-            &Cell(ref elm, _) => elm.clone(),
+            &Nil => Err(PfdsError::Empty),
+            &Cell(ref elm, _) => Ok(elm.clone()),
         }
     }
 
-    fn tail(&self) -> Self {
+    fn tail(&self) -> Result<Self, PfdsError> {
         match self {
-            &Nil => panic!("List is empty!"),
-            &Cell(_, box ref next) => next.clone(),
+            &Nil => Err(PfdsError::Empty),
+            &Cell(_, ref next) => Ok((**next).clone()),
         }
     }
 
     fn concat(&self, ys: Self) -> Self {
         match self {
             &Nil => ys, 
-            &Cell(ref head, box ref tail) => tail.concat(ys).cons(head.clone()),
+            &Cell(ref head, ref tail) => tail.concat(ys).cons(head.clone()),
         }
     }
 
-    fn update(&self, index: i32, x: T) -> Self {
+    fn update(&self, index: i32, x: T) -> Result<Self, PfdsError> {
         match self {
-            &Nil => panic!("List is empty!"),
-            &Cell(ref head, box ref tail) => {
+            &Nil => Err(PfdsError::IndexOutOfRange(index)),
+            _ if index < 0 => Err(PfdsError::IndexOutOfRange(index)),
+            &Cell(ref head, ref tail) => {
                 match index {
-                    0 => tail.cons(x),
-                    index => tail.update(index - 1, x).cons(head.clone()),
+                    0 => Ok(tail.cons(x)),
+                    _ => {
+                        tail.update(index - 1, x)
+                            .map(|tail| tail.cons(head.clone()))
+                            .map_err(|_| PfdsError::IndexOutOfRange(index))
+                    }
                 }
             }
         }
@@ -64,13 +81,13 @@ impl<T: Clone + Debug> List<T> for Stack<T> {
 
 impl<T: Clone + Debug> Stack<T> {
     pub fn new(x: T) -> Self {
-        Cell(x, box Nil)
+        Cell(x, Rc::new(Nil))
     }
 
-    fn suffixes(&self) -> Stack<Self> {
+    pub fn suffixes(&self) -> Stack<Self> {
         match self {
             &Nil => Stack::empty(),
-            &Cell(_, box ref tail) => tail.suffixes().cons(self.clone()),
+            &Cell(_, ref tail) => tail.suffixes().cons(self.clone()),
         }
     }
 
@@ -103,7 +120,7 @@ impl<T: Clone + Debug> Stack<T> {
     pub fn reverse(&self) -> Self {
         match self {
             &Nil => self.clone(),
-            &Cell(ref head, box ref tail) => {
+            &Cell(ref head, ref tail) => {
                 if tail.is_empty() {
                     Stack::new(head.clone())
                 } else {
@@ -118,17 +135,28 @@ impl<T: Clone + Debug> Stack<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_drop_long() {
+        let mut actual = Stack::empty();
+        for i in 0..300000 {
+            actual = actual.cons(i);
+        }
+        let shared = actual.tail().unwrap();
+        drop(actual);
+        assert!(shared.head() == Ok(299998));
+    }
+
     #[test]
     fn test_reverse() {
         let actual = Stack::empty().cons(1).cons(2).cons(3).reverse();
-        let expect = Cell(1, box Cell(2, box Cell(3, box Nil)));
+        let expect = Cell(1, Rc::new(Cell(2, Rc::new(Cell(3, Rc::new(Nil))))));
         assert!(actual.head() == expect.head());
     }
 
     #[test]
     fn test_map() {
         let actual = Stack::empty().cons(1).cons(2).cons(3).map(&|x| x + 1);
-        let expect = Cell(4, box Cell(3, box Cell(2, box Nil)));
+        let expect = Cell(4, Rc::new(Cell(3, Rc::new(Cell(2, Rc::new(Nil))))));
         assert!(actual == expect);
     }
 
@@ -153,44 +181,74 @@ mod tests {
     #[test]
     fn test_cons() {
         let actual = Stack::empty().cons(1).cons(2).cons(3);
-        let expect = Cell(3, box Cell(2, box Cell(1, box Nil)));
+        let expect = Cell(3, Rc::new(Cell(2, Rc::new(Cell(1, Rc::new(Nil))))));
         assert!(actual == expect);
     }
 
     #[test]
     fn test_head() {
         let list = Stack::empty().cons(1);
-        assert!(list.head() == 1);
+        assert!(list.head() == Ok(1));
     }
 
     #[test]
-    #[should_panic]
     fn test_empty_head() {
         let list: Stack<i32> = Stack::empty();
-        list.head();
+        assert!(list.head() == Err(PfdsError::Empty));
+        assert!(list.tail() == Err(PfdsError::Empty));
+        assert!(list.uncons() == Err(PfdsError::Empty));
+    }
+
+    #[test]
+    fn test_get() {
+        let list = Stack::empty().cons(1).cons(2).cons(3);
+        assert!(list.get(0) == Ok(3));
+        assert!(list.get(2) == Ok(1));
+        assert!(list.get(3) == Err(PfdsError::IndexOutOfRange(3)));
+        assert!(list.get(-1) == Err(PfdsError::IndexOutOfRange(-1)));
     }
 
     #[test]
     fn test_tail() {
         let actual = Stack::empty().cons(1).cons(2).cons(3);
-        let expect = Cell(2, box Cell(1, box Nil));
-        assert!(actual.tail() == expect);
+        let expect = Cell(2, Rc::new(Cell(1, Rc::new(Nil))));
+        assert!(actual.tail() == Ok(expect));
     }
 
     #[test]
     fn test_concat() {
         let actual = Stack::empty().cons(2).cons(1).concat(Stack::empty().cons(4).cons(3));
-        let expect =
-            Cell(1,
-                        box Cell(2, box Cell(3, box Cell(4, box Nil))));
+        let expect = Stack::empty().cons(4).cons(3).cons(2).cons(1);
         assert!(actual == expect);
     }
 
     #[test]
     fn test_update() {
-        let actual = Stack::empty().cons(1).cons(2).cons(3).update(1, 9);
-        let expect = Cell(3, box Cell(9, box Cell(1, box Nil)));
-        assert!(actual == expect);
+        let actual = Stack::empty().cons(1).cons(2).cons(3);
+        let expect = Cell(3, Rc::new(Cell(9, Rc::new(Cell(1, Rc::new(Nil))))));
+        assert!(actual.update(1, 9) == Ok(expect));
+        assert!(actual.update(3, 9) == Err(PfdsError::IndexOutOfRange(3)));
+        assert!(actual.update(-1, 9) == Err(PfdsError::IndexOutOfRange(-1)));
+    }
+
+    #[test]
+    fn test_sharing() {
+        let xs = Stack::empty().cons(1).cons(2);
+        let ys = xs.cons(3);
+        match (&xs, &ys.tail().unwrap()) {
+            (&Cell(_, ref xs_tail), &Cell(_, ref ys_tail)) => {
+                assert!(Rc::ptr_eq(xs_tail, ys_tail))
+            }
+            _ => unreachable!(),
+        }
+
+        let suffixes = ys.suffixes();
+        match (&xs, &suffixes.get(1).unwrap()) {
+            (&Cell(_, ref xs_tail), &Cell(_, ref suffix_tail)) => {
+                assert!(Rc::ptr_eq(xs_tail, suffix_tail))
+            }
+            _ => unreachable!(),
+        }
     }
 
     #[test]
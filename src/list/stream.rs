@@ -1,157 +1,709 @@
 use std::fmt::Debug;
-use lazy::Susp;
+use std::ops::Add;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use heap::Heap;
+use heap::leftist::LeftistHeap;
+use lazy::{Susp, Step};
+use lazy::Step::*;
+use list::unlink;
 
 use self::StreamCell::*;
 
 #[derive(Debug, PartialEq, Clone)]
-enum StreamCell<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+pub enum StreamCell<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
     Nil,
-    Cons(T, Box<Stream<'a, T>>),
+    Cons(T, Stream<'a, T>),
 }
 
 impl<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> StreamCell<'a, T> {
     fn drop_impl(&self, n: i32) -> Self {
-        match self {
-            &Nil => Nil,
-            _ if n == 0 => self.clone(),
-            &Cons(_, box ref tail) => (**tail).drop_impl(n - 1),
+        let mut cell = self.clone();
+        for _ in 0..n {
+            cell = match cell {
+                Nil => return Nil,
+                Cons(_, ref tail) => tail.force().clone(),
+            };
         }
+        cell
     }
 
     fn reverse_impl(&self, other: &Self) -> Self {
-        let that = other.clone();
-        match (self, other) {
-            (&Nil, _) => other.clone(),
-            (&Cons(ref head, box ref tail), _) => {
-                let last = Cons(head.clone(), box susp!(that.clone()));
-                (**tail).reverse_impl(&last)
-            }
+        let mut reversed = other.clone();
+        let mut cell = self.clone();
+        loop {
+            cell = match cell {
+                Nil => return reversed,
+                Cons(ref head, ref tail) => {
+                    reversed = Cons(head.clone(), Stream(Susp::from_value(reversed)));
+                    tail.force().clone()
+                }
+            };
         }
     }
 
     fn insert(&self, x: &T) -> Self {
         match self {
-            &Nil => Cons(x.clone(), box Stream::empty()),
-            &Cons(ref head, box ref tail) => {
+            &Nil => Cons(x.clone(), Stream::empty()),
+            &Cons(ref head, ref tail) => {
                 if x < head {
                     let head = head.clone();
-                    let tail = tail.clone();
-                    Cons(x.clone(), box susp!(tail.insert(&head)))
+                    Cons(x.clone(), tail.bind(move |cell| Done(cell.insert(&head))))
                 } else {
-                    let tail = tail.clone();
                     let x = x.clone();
-                    Cons(head.clone(), box susp!(tail.insert(&x)))
+                    Cons(head.clone(), tail.bind(move |cell| Done(cell.insert(&x))))
                 }
             }
         }
     }
 
-    fn insert_sort_impl(&self, sorted: &Self, x: &T) -> Self {
-        match self {
-            &Nil => sorted.clone(),
-            &Cons(ref head, ref tail) => {
-                if x < head {
-                    tail.insert_sort_impl(&sorted.insert(x), head)
-                } else {
-                    tail.insert_sort_impl(&sorted.insert(head), x)
+    fn insert_sort_impl(&self) -> Self {
+        let mut sorted = Nil;
+        let mut cell = self.clone();
+        loop {
+            cell = match cell {
+                Nil => return sorted,
+                Cons(ref head, ref tail) => {
+                    sorted = sorted.insert(head);
+                    tail.force().clone()
                 }
-            }
+            };
         }
     }
 }
 
-type Stream<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> = Susp<'a, StreamCell<'a, T>>;
+// Only evaluated tails are unlinked; a suspended one drops with its closure.
+impl<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> Drop for StreamCell<'a, T> {
+    fn drop(&mut self) {
+        unlink(self, |cell| match *cell {
+                   Nil => None,
+                   Cons(_, ref mut tail) => tail.0.take_unique(),
+               });
+    }
+}
+
+// A lazy list: every cell is a suspension that is evaluated at most once, however many
+// clones of the stream share it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Stream<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone>(Susp<'a, StreamCell<'a, T>>);
 
 impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Stream<'a, T> {
-    fn empty() -> Self {
-        susp!(Nil)
+    pub fn empty() -> Self {
+        Stream(Susp::from_value(Nil))
     }
 
-    fn cons(&self, x: &T) -> Self {
-        let this = self.clone();
-        let x = x.clone();
-        susp!({
-                  match *this {
-                      Nil => Cons(x.clone(), box Stream::empty()),
-                      Cons(ref head, ref tail) => Cons(head.clone(), box tail.cons(&x)),
-                  }
-              })
+    // Suspends the computation of the first cell.
+    pub fn lazy<F>(f: F) -> Self where F: 'a + FnOnce() -> StreamCell<'a, T> {
+        Stream(Susp::new(f))
+    }
+
+    // A stream defined in terms of itself; see `Susp::fix` for when its cells are freed.
+    pub fn fix<F>(f: F) -> Self where F: 'a + FnOnce(&Self) -> StreamCell<'a, T> {
+        Stream(Susp::fix(move |this: &Susp<'a, StreamCell<'a, T>>| f(&Stream(this.clone()))))
+    }
+
+    // x, f(x), f(f(x)), ...
+    pub fn iterate<F>(seed: T, f: F) -> Self where F: 'a + Fn(&T) -> T {
+        Stream::successors(seed.clone(), Rc::new(f)).cons(&seed)
+    }
+
+    fn successors<F>(x: T, f: Rc<F>) -> Self where F: 'a + Fn(&T) -> T {
+        Stream::lazy(move || {
+                         let next = f(&x);
+                         Cons(next.clone(), Stream::successors(next, f))
+                     })
+    }
+
+    // Produces elements from `state` until `f` returns None.
+    pub fn unfold<S, F>(state: S, f: F) -> Self
+        where S: 'a,
+              F: 'a + Fn(S) -> Option<(T, S)>
+    {
+        Stream::unfold_impl(state, Rc::new(f))
+    }
+
+    fn unfold_impl<S, F>(state: S, f: Rc<F>) -> Self
+        where S: 'a,
+              F: 'a + Fn(S) -> Option<(T, S)>
+    {
+        Stream::lazy(move || match f(state) {
+                         Some((x, next)) => Cons(x, Stream::unfold_impl(next, f.clone())),
+                         None => Nil,
+                     })
     }
 
-    fn concat(&self, other: &Self) -> Self {
+    pub fn repeat(x: T) -> Self {
+        Stream::iterate(x, |x| x.clone())
+    }
+
+    // Repeats this stream forever; the cycle of an empty stream is empty.
+    pub fn cycle(&self) -> Self {
         let this = self.clone();
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(_, _) => Then(this.concat(&this.cycle()).0),
+                  })
+    }
+
+    // start, start + 1, ... up to but not including `end`.
+    pub fn range(start: T, end: T) -> Self where T: 'a + Add<Output = T> + From<u8> {
+        Stream::unfold(start, move |x| if x < end {
+            let next = x.clone() + T::from(1);
+            Some((x, next))
+        } else {
+            None
+        })
+    }
+
+    pub fn count_from(start: T) -> Self where T: 'a + Add<Output = T> + From<u8> {
+        Stream::iterate(start, |x| x.clone() + T::from(1))
+    }
+
+    // Pulls from `iter` only as cells are forced. Each element is pulled once and memoized,
+    // so the stream can be replayed, and shared, after the iterator is exhausted.
+    pub fn from_iter<I>(iter: I) -> Self where I: IntoIterator<Item = T>, I::IntoIter: 'a {
+        Stream::pull(Rc::new(RefCell::new(iter.into_iter())))
+    }
+
+    fn pull<I>(iter: Rc<RefCell<I>>) -> Self where I: 'a + Iterator<Item = T> {
+        Stream::lazy(move || {
+                         let next = iter.borrow_mut().next();
+                         match next {
+                             Some(x) => Cons(x, Stream::pull(iter)),
+                             None => Nil,
+                         }
+                     })
+    }
+
+    // Forces one cell per call to `next`, and nothing beyond what has been consumed.
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter { rest: self.clone() }
+    }
+
+    pub fn force(&self) -> &StreamCell<'a, T> {
+        &*self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self.force() {
+            &Nil => true,
+            &Cons(_, _) => false,
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        match self.force() {
+            &Nil => None,
+            &Cons(ref head, _) => Some(head),
+        }
+    }
+
+    pub fn tail(&self) -> Option<Self> {
+        match self.force() {
+            &Nil => None,
+            &Cons(_, ref tail) => Some(tail.clone()),
+        }
+    }
+
+    // O(1): the new cell is already evaluated and the stream itself is not forced.
+    pub fn cons(&self, x: &T) -> Self {
+        Stream(Susp::from_value(Cons(x.clone(), self.clone())))
+    }
+
+    pub fn snoc(&self, x: &T) -> Self {
+        self.concat(&Stream::empty().cons(x))
+    }
+
+    fn bind<U, K>(&self, k: K) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              K: 'a + FnOnce(&StreamCell<'a, T>) -> Step<'a, StreamCell<'a, U>>
+    {
+        Stream(self.0.bind(k))
+    }
+
+    pub fn concat(&self, other: &Self) -> Self {
         let that = other.clone();
-        susp!({
-                  match *this {
-                      Nil => (*that).clone(),
-                      Cons(ref head, ref tail) => Cons(head.clone(), box tail.concat(&that)),
-                  }
-              })
+        self.bind(move |cell| match cell {
+                      &Nil => Then(that.0),
+                      &Cons(ref head, ref tail) => Done(Cons(head.clone(), tail.concat(&that))),
+                  })
     }
 
-    fn take(&self, n: i32) -> Self {
-        let this = self.clone();
-        susp!({
-                  match *this {
-                      Nil => Nil,
-                      _ if n == 0 => Nil,
-                      Cons(ref head, ref tail) => Cons(head.clone(), box tail.take(n - 1)),
-                  }
-              })
+    pub fn take(&self, n: i32) -> Self {
+        if n <= 0 {
+            return Stream::empty();
+        }
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref head, ref tail) => Done(Cons(head.clone(), tail.take(n - 1))),
+                  })
     }
 
-    fn drop_nth(&self, n: i32) -> Self {
-        let this = self.clone();
-        susp!((*this).drop_impl(n))
+    pub fn drop_nth(&self, n: i32) -> Self {
+        self.bind(move |cell| Done(cell.drop_impl(n)))
     }
 
-    fn reverse(&self) -> Self {
-        let this = self.clone();
-        susp!((*this).reverse_impl(&Nil))
+    pub fn reverse(&self) -> Self {
+        self.bind(|cell| Done(cell.reverse_impl(&Nil)))
+    }
+
+    // Lazy insertion sort: the first element costs O(n) and every further one O(n), so
+    // `insert_sort().take(k)` costs O(n·k).
+    pub fn insert_sort(&self) -> Self {
+        self.bind(|cell| Done(cell.insert_sort_impl()))
+    }
+
+    // Lazy bottom-up merge sort. The first element costs O(n) and every further one
+    // O(log n), so `merge_sort().take(k)` costs O(n + k log n). Equal elements keep their order.
+    pub fn merge_sort(&self) -> Self {
+        self.bind(|cell| {
+            let cells = Stream(Susp::from_value(cell.clone()));
+            let mut runs = cells.iter().map(|x| Stream::empty().cons(&x)).collect::<Vec<_>>();
+            while runs.len() > 1 {
+                let mut merged = Vec::with_capacity((runs.len() + 1) / 2);
+                let mut pairs = runs.into_iter();
+                while let Some(xs) = pairs.next() {
+                    merged.push(match pairs.next() {
+                                    Some(ys) => xs.merge(&ys),
+                                    None => xs,
+                                });
+                }
+                runs = merged;
+            }
+            match runs.pop() {
+                Some(sorted) => Then(sorted.0),
+                None => Done(Nil),
+            }
+        })
+    }
+
+    pub fn sort(&self) -> Self {
+        self.merge_sort()
     }
 
-    fn insert_sort(&self) -> Self {
+    // Merges two sorted streams into one, taking from this one first on ties.
+    pub fn merge(&self, other: &Self) -> Self {
         let this = self.clone();
-        susp!({
-                  match &*this.take(1) {
-                      &Nil => Nil,
-                      &Cons(ref head, _) => (*this).insert_sort_impl(&Nil, &head.clone()),
-                  }
-              })
+        let that = other.clone();
+        self.bind(move |cell| match cell {
+                      &Nil => Then(that.0),
+                      &Cons(ref x, ref xs) => {
+                          let (x, xs, other) = (x.clone(), xs.clone(), that.clone());
+                          Then(other.bind(move |cell| match cell {
+                                              &Nil => Done(Cons(x, xs)),
+                                              &Cons(ref y, ref ys) if *y < x => {
+                                                  Done(Cons(y.clone(), this.merge(ys)))
+                                              }
+                                              &Cons(_, _) => Done(Cons(x, xs.merge(&that))),
+                                          })
+                                   .0)
+                      }
+                  })
+    }
+
+    // Merges any number of sorted streams, finite or infinite, through a leftist heap keyed by
+    // their heads. Each stream is forced only as far as the merged output demands.
+    pub fn merge_all<I>(streams: I) -> Self
+        where T: Ord,
+              I: IntoIterator<Item = Self>
+    {
+        let pending = streams.into_iter().enumerate().collect();
+        Stream::merge_heap(LeftistHeap::empty(), pending)
+    }
+
+    // `pending` streams have not had their heads forced yet; they join the heap only when the
+    // next cell is forced, which keeps self-referential merges from forcing themselves.
+    fn merge_heap(heap: LeftistHeap<Head<'a, T>>, mut pending: Vec<(usize, Self)>) -> Self
+        where T: Ord
+    {
+        match pending.pop() {
+            Some((seq, xs)) => {
+                xs.bind(move |cell| {
+                    let heap = match cell {
+                        &Nil => heap,
+                        &Cons(ref x, ref rest) => {
+                            heap.insert(Head {
+                                            head: x.clone(),
+                                            seq: seq,
+                                            rest: rest.clone(),
+                                        })
+                        }
+                    };
+                    Then(Stream::merge_heap(heap, pending).0)
+                })
+            }
+            None => {
+                Stream::lazy(move || match heap.find_min() {
+                                 None => Nil,
+                                 Some(min) => {
+                                     let pending = vec![(min.seq, min.rest)];
+                                     Cons(min.head, Stream::merge_heap(heap.delete_min(), pending))
+                                 }
+                             })
+            }
+        }
     }
+
+    // Drops consecutive duplicates; on a sorted stream this leaves every element once.
+    pub fn dedup(&self) -> Self {
+        self.bind(|cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref x, ref xs) => {
+                          let first = x.clone();
+                          let rest = xs.drop_while(move |y| *y == first).dedup();
+                          Done(Cons(x.clone(), rest))
+                      }
+                  })
+    }
+}
+
+// A stream in `Stream::merge_all`'s heap, ordered by its head and then by its position among
+// the merged streams, so that ties come out in a stable order.
+#[derive(Debug, Clone)]
+struct Head<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+    head: T,
+    seq: usize,
+    rest: Stream<'a, T>,
 }
 
+impl<'a, T: Ord + Debug + Clone> PartialEq for Head<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.head == other.head && self.seq == other.seq
+    }
+}
+
+impl<'a, T: Ord + Debug + Clone> Eq for Head<'a, T> {}
+
+impl<'a, T: Ord + Debug + Clone> PartialOrd for Head<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T: Ord + Debug + Clone> Ord for Head<'a, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.head.cmp(&other.head).then(self.seq.cmp(&other.seq))
+    }
+}
+
+// Incremental operations: forcing a cell of the result forces only as much of the input as
+// that one cell needs, so they compose on infinite streams.
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Stream<'a, T> {
+    pub fn map<U, F>(&self, f: F) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&T) -> U
+    {
+        self.map_impl(Rc::new(f))
+    }
+
+    fn map_impl<U, F>(&self, f: Rc<F>) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&T) -> U
+    {
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref x, ref xs) => Done(Cons(f(x), xs.map_impl(f.clone()))),
+                  })
+    }
+
+    pub fn filter<P>(&self, p: P) -> Self where P: 'a + Fn(&T) -> bool {
+        self.filter_impl(Rc::new(p))
+    }
+
+    fn filter_impl<P>(&self, p: Rc<P>) -> Self where P: 'a + Fn(&T) -> bool {
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref x, ref xs) if p(x) => Done(Cons(x.clone(), xs.filter_impl(p))),
+                      &Cons(_, ref xs) => Then(xs.filter_impl(p).0),
+                  })
+    }
+
+    // Forces neither stream until the result is, so it can be used to define a stream in terms
+    // of itself through `Stream::fix`.
+    pub fn zip_with<U, V, F>(&self, other: &Stream<'a, U>, f: F) -> Stream<'a, V>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              V: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&T, &U) -> V
+    {
+        self.zip_with_impl(other, Rc::new(f))
+    }
+
+    fn zip_with_impl<U, V, F>(&self, other: &Stream<'a, U>, f: Rc<F>) -> Stream<'a, V>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              V: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&T, &U) -> V
+    {
+        let that = other.clone();
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref x, ref xs) => {
+                          let (x, xs) = (x.clone(), xs.clone());
+                          Then(that.bind(move |cell| match cell {
+                                             &Nil => Done(Nil),
+                                             &Cons(ref y, ref ys) => {
+                                                 Done(Cons(f(&x, y), xs.zip_with_impl(ys, f)))
+                                             }
+                                         })
+                                   .0)
+                      }
+                  })
+    }
+
+    // init, f(init, x0), f(f(init, x0), x1), ...
+    pub fn scan<U, F>(&self, init: U, f: F) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&U, &T) -> U
+    {
+        self.scan_impl(init.clone(), Rc::new(f)).cons(&init)
+    }
+
+    fn scan_impl<U, F>(&self, acc: U, f: Rc<F>) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&U, &T) -> U
+    {
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref x, ref xs) => {
+                          let next = f(&acc, x);
+                          Done(Cons(next.clone(), xs.scan_impl(next, f)))
+                      }
+                  })
+    }
+
+    pub fn take_while<P>(&self, p: P) -> Self where P: 'a + Fn(&T) -> bool {
+        self.take_while_impl(Rc::new(p))
+    }
+
+    fn take_while_impl<P>(&self, p: Rc<P>) -> Self where P: 'a + Fn(&T) -> bool {
+        self.bind(move |cell| match cell {
+                      &Cons(ref x, ref xs) if p(x) => {
+                          Done(Cons(x.clone(), xs.take_while_impl(p.clone())))
+                      }
+                      _ => Done(Nil),
+                  })
+    }
+
+    pub fn drop_while<P>(&self, p: P) -> Self where P: 'a + Fn(&T) -> bool {
+        self.drop_while_impl(Rc::new(p))
+    }
+
+    fn drop_while_impl<P>(&self, p: Rc<P>) -> Self where P: 'a + Fn(&T) -> bool {
+        self.bind(move |cell| match cell {
+                      &Cons(ref x, ref xs) if p(x) => Then(xs.drop_while_impl(p).0),
+                      cell => Done(cell.clone()),
+                  })
+    }
+
+    pub fn flat_map<U, F>(&self, f: F) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&T) -> Stream<'a, U>
+    {
+        self.flat_map_impl(Rc::new(f))
+    }
+
+    fn flat_map_impl<U, F>(&self, f: Rc<F>) -> Stream<'a, U>
+        where U: 'a + Debug + PartialEq + PartialOrd + Clone,
+              F: 'a + Fn(&T) -> Stream<'a, U>
+    {
+        self.bind(move |cell| match cell {
+                      &Nil => Done(Nil),
+                      &Cons(ref x, ref xs) => Then(f(x).concat(&xs.flat_map_impl(f.clone())).0),
+                  })
+    }
+
+    // Alternates between the two streams, starting with this one.
+    pub fn interleave(&self, other: &Self) -> Self {
+        let that = other.clone();
+        self.bind(move |cell| match cell {
+                      &Nil => Then(that.0),
+                      &Cons(ref x, ref xs) => Done(Cons(x.clone(), that.interleave(xs))),
+                  })
+    }
+
+    // Groups consecutive elements into vectors of `n`; the last one may be shorter.
+    pub fn chunks(&self, n: i32) -> Stream<'a, Vec<T>> {
+        assert!(n > 0, "chunk size must be positive");
+        self.chunks_impl(Vec::new(), n)
+    }
+
+    fn chunks_impl(&self, chunk: Vec<T>, n: i32) -> Stream<'a, Vec<T>> {
+        self.bind(move |cell| {
+            let mut chunk = chunk;
+            match cell {
+                &Nil if chunk.is_empty() => Done(Nil),
+                &Nil => Done(Cons(chunk, Stream::empty())),
+                &Cons(ref x, ref xs) => {
+                    chunk.push(x.clone());
+                    if chunk.len() == n as usize {
+                        Done(Cons(chunk, xs.chunks(n)))
+                    } else {
+                        Then(xs.chunks_impl(chunk, n).0)
+                    }
+                }
+            }
+        })
+    }
+}
+
+pub struct Iter<'a, T: 'a + Debug + PartialEq + PartialOrd + Clone> {
+    rest: Stream<'a, T>,
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (x, rest) = match self.rest.force() {
+            &Nil => return None,
+            &Cons(ref x, ref xs) => (x.clone(), xs.clone()),
+        };
+        self.rest = rest;
+        Some(x)
+    }
+}
+
+impl<'a, T: Debug + PartialEq + PartialOrd + Clone> IntoIterator for Stream<'a, T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        Iter { rest: self }
+    }
+}
+
+impl<'s, 'a, T: Debug + PartialEq + PartialOrd + Clone> IntoIterator for &'s Stream<'a, T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use std::rc::Rc;
+    use std::cell::Cell;
 
     #[test]
     fn test_insert_sort() {
         let actual = Stream::empty().cons(&3).cons(&1).cons(&2).insert_sort();
         assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+
+        let actual = Stream::empty().cons(&2).cons(&3).cons(&1).insert_sort();
+        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_merge_sort() {
+        let actual = Stream::empty().cons(&3).cons(&1).cons(&2).merge_sort();
+        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+
+        let actual = Stream::from_iter(vec![5, 1, 4, 1, 5, 9, 2, 6]).sort();
+        assert!(actual.iter().collect::<Vec<_>>() == vec![1, 1, 2, 4, 5, 5, 6, 9]);
+        assert!(Stream::<i32>::empty().sort().is_empty());
+    }
+
+    #[derive(Debug, Clone)]
+    struct Compared(u64, Rc<Cell<usize>>);
+
+    impl PartialEq for Compared {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl PartialOrd for Compared {
+        fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+            self.1.set(self.1.get() + 1);
+            self.0.partial_cmp(&other.0)
+        }
+    }
+
+    fn shuffled<'a>(n: u64, comparisons: &Rc<Cell<usize>>)
+                    -> Stream<'a, Compared> {
+        let comparisons = comparisons.clone();
+        Stream::range(0, n).map(move |i| Compared(i * 7919 % n, comparisons.clone()))
+    }
+
+    #[test]
+    fn test_top_k() {
+        let (n, k) = (2000, 10);
+        let comparisons = Rc::new(Cell::new(0));
+        let expected = (0..k).collect::<Vec<_>>();
+
+        let actual = shuffled(n, &comparisons).insert_sort().take(k as i32);
+        assert!(actual.iter().map(|x| x.0).collect::<Vec<_>>() == expected);
+        assert!(comparisons.get() <= (n * k) as usize);
+
+        comparisons.set(0);
+        let actual = shuffled(n, &comparisons).merge_sort().take(k as i32);
+        assert!(actual.iter().map(|x| x.0).collect::<Vec<_>>() == expected);
+        assert!(comparisons.get() <= (n + k * 11) as usize);
+
+        comparisons.set(0);
+        assert!(shuffled(n, &comparisons).merge_sort().iter().count() == n as usize);
+        assert!(comparisons.get() > (n * 8) as usize);
     }
 
     #[test]
     fn test_reverse() {
         let actual = Stream::empty().cons(&1).cons(&2).cons(&3).reverse();
-        assert!(is_match_with_vec(actual, vec![3, 2, 1]));
+        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
     }
 
     #[test]
     fn test_drop() {
         let actual = Stream::empty().cons(&1).cons(&2).cons(&3).drop_nth(2);
-        assert!(is_match_with_vec(actual, vec![3]));
+        assert!(is_match_with_vec(actual, vec![1]));
     }
 
     #[test]
     fn test_take() {
         let actual = Stream::empty().cons(&1).cons(&2).cons(&3).take(2);
-        assert!(is_match_with_vec(actual, vec![1, 2]));
+        assert!(is_match_with_vec(actual, vec![3, 2]));
     }
 
     #[test]
     fn test_cons() {
         let actual = Stream::empty().cons(&1).cons(&2).cons(&3);
-        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+        assert!(is_match_with_vec(actual, vec![3, 2, 1]));
+    }
+
+    #[test]
+    fn test_cons_is_lazy() {
+        let forced = Rc::new(Cell::new(false));
+        let flag = forced.clone();
+        let tail = Stream::lazy(move || {
+                                    flag.set(true);
+                                    Nil
+                                });
+        let actual = tail.cons(&1).cons(&2);
+        assert!(actual.head() == Some(&2));
+        assert!(!forced.get());
+        assert!(actual.tail().unwrap().tail().unwrap().is_empty());
+        assert!(forced.get());
+    }
+
+    #[test]
+    fn test_snoc() {
+        let actual = Stream::empty().snoc(&1).snoc(&2).cons(&0).snoc(&3);
+        assert!(is_match_with_vec(actual, vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let actual = Stream::empty().cons(&1).cons(&2);
+        assert!(!actual.is_empty());
+        assert!(actual.head() == Some(&2));
+        let tail = actual.tail().unwrap();
+        assert!(tail.head() == Some(&1));
+        let tail = tail.tail().unwrap();
+        assert!(tail.is_empty());
+        assert!(tail.head() == None);
+        assert!(tail.tail() == None);
     }
 
     #[test]
@@ -160,18 +712,479 @@ mod tests {
         let actual_2 = Stream::empty().cons(&4).cons(&5).cons(&6);
 
         let actual = actual_1.concat(&actual_2);
-        assert!(is_match_with_vec(actual, vec![1, 2, 3, 4, 5, 6]));
+        assert!(is_match_with_vec(actual, vec![3, 2, 1, 6, 5, 4]));
+    }
+
+    #[test]
+    fn test_zip_with() {
+        let actual_1 = Stream::empty().cons(&1).cons(&2).cons(&3);
+        let actual_2 = Stream::empty().cons(&10).cons(&20);
+        let actual = actual_1.zip_with(&actual_2, |x, y| x + y);
+        assert!(is_match_with_vec(actual, vec![23, 12]));
+    }
+
+    #[test]
+    fn test_fibs() {
+        let additions = Rc::new(Cell::new(0));
+        let counter = additions.clone();
+        let fibs = Stream::fix(move |fibs: &Stream<u64>| {
+            let counter = counter.clone();
+            let rest = fibs.zip_with(&fibs.drop_nth(1), move |x, y| {
+                counter.set(counter.get() + 1);
+                x + y
+            });
+            Cons(0, rest.cons(&1))
+        });
+        assert!(is_match_with_vec(fibs.take(10), vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]));
+        assert!(additions.get() == 8);
+        assert!(is_match_with_vec(fibs.take(10), vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]));
+        assert!(additions.get() == 8);
+    }
+
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Live(Rc<Cell<i32>>);
+
+    impl Clone for Live {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            Live(self.0.clone())
+        }
+    }
+
+    impl Drop for Live {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() - 1);
+        }
+    }
+
+    #[test]
+    fn test_fix_drops_forced_cells() {
+        let live = Rc::new(Cell::new(1));
+        let actual = {
+            let x = Live(live.clone());
+            Stream::fix(move |xs: &Stream<Live>| Cons(x, xs.clone()))
+        };
+        let rest = actual.drop_nth(10);
+        assert!(rest.head().is_some());
+        assert!(live.get() > 1);
+        drop(actual);
+        drop(rest);
+        assert!(live.get() == 0);
+    }
+
+    fn scale<'a>(xs: &Stream<'a, u64>, n: u64) -> Stream<'a, u64> {
+        let xs = xs.clone();
+        Stream::lazy(move || match xs.force() {
+                         &Nil => Nil,
+                         &Cons(ref head, ref tail) => Cons(head * n, scale(tail, n)),
+                     })
+    }
+
+    fn union<'a>(xs: &Stream<'a, u64>, ys: &Stream<'a, u64>) -> Stream<'a, u64> {
+        let (xs, ys) = (xs.clone(), ys.clone());
+        Stream::lazy(move || match (xs.force(), ys.force()) {
+                         (&Nil, other) | (other, &Nil) => other.clone(),
+                         (&Cons(ref x, ref xt), &Cons(ref y, ref yt)) => {
+                             if x < y {
+                                 Cons(*x, union(xt, &ys))
+                             } else if y < x {
+                                 Cons(*y, union(&xs, yt))
+                             } else {
+                                 Cons(*x, union(xt, yt))
+                             }
+                         }
+                     })
+    }
+
+    #[test]
+    fn test_hamming() {
+        let hamming = Stream::fix(|hamming: &Stream<u64>| {
+            let rest = union(&scale(hamming, 2), &union(&scale(hamming, 3), &scale(hamming, 5)));
+            Cons(1, rest)
+        });
+        let expected = vec![1, 2, 3, 4, 5, 6, 8, 9, 10, 12, 15, 16, 18, 20, 24, 25, 27, 30];
+        assert!(is_match_with_vec(hamming.take(18), expected));
+        assert!(is_match_with_vec(hamming.drop_nth(1690).take(1), vec![2125764000]));
+    }
+
+    #[test]
+    fn test_hamming_merge_all() {
+        let hamming = Stream::fix(|hamming: &Stream<u64>| {
+            let scaled = vec![2, 3, 5].into_iter().map(|n| hamming.map(move |x| x * n));
+            Cons(1, Stream::merge_all(scaled).dedup())
+        });
+        let expected = vec![1, 2, 3, 4, 5, 6, 8, 9, 10, 12, 15, 16, 18, 20, 24, 25, 27, 30];
+        assert!(is_match_with_vec(hamming.take(18), expected));
+        assert!(is_match_with_vec(hamming.drop_nth(1690).take(1), vec![2125764000]));
+    }
+
+    #[test]
+    fn test_merge() {
+        let evens = Stream::iterate(0, |x| x + 2);
+        let threes = Stream::iterate(0, |x| x + 3);
+        let actual = evens.merge(&threes);
+        assert!(is_match_with_vec(actual.take(8), vec![0, 0, 2, 3, 4, 6, 6, 8]));
+        assert!(is_match_with_vec(actual.dedup().take(6), vec![0, 2, 3, 4, 6, 8]));
+
+        let actual = Stream::range(0, 3).merge(&Stream::empty());
+        assert!(actual.iter().collect::<Vec<_>>() == vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_merge_all() {
+        let evaluated = Rc::new(Cell::new(0));
+        let streams = vec![counted(&evaluated).map(|x| x * 3),
+                           Stream::empty(),
+                           Stream::range(1, 4),
+                           Stream::iterate(0, |x| x + 5)];
+        let actual = Stream::merge_all(streams);
+        assert!(evaluated.get() == 0);
+        assert!(is_match_with_vec(actual.take(7), vec![0, 0, 1, 2, 3, 3, 5]));
+        assert!(evaluated.get() == 3);
+        assert!(is_match_with_vec(actual.dedup().take(6), vec![0, 1, 2, 3, 5, 6]));
+
+        assert!(Stream::<i32>::merge_all(vec![]).is_empty());
+        let actual = Stream::merge_all(vec![Stream::range(0, 2), Stream::range(0, 2)]);
+        assert!(actual.iter().collect::<Vec<_>>() == vec![0, 0, 1, 1]);
+    }
+
+    // Ordered by `key` alone, so that `tag` tells apart elements that compare equal.
+    #[derive(Debug, Clone)]
+    struct Tagged {
+        key: i32,
+        tag: char,
+    }
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Tagged {}
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    fn tagged<'a>(keys: &[i32], tag: char) -> Stream<'a, Tagged> {
+        let cells = keys.iter().map(|&key| Tagged { key: key, tag: tag });
+        Stream::from_iter(cells.collect::<Vec<_>>())
+    }
+
+    fn tags<'a>(xs: Stream<'a, Tagged>) -> String {
+        xs.iter().map(|x| x.tag).collect()
+    }
+
+    #[test]
+    fn test_merge_is_stable() {
+        assert!(tags(tagged(&[0, 1], 'a').merge(&tagged(&[0, 1], 'b'))) == "abab");
+        assert!(tags(tagged(&[0, 1], 'b').merge(&tagged(&[0, 1], 'a'))) == "baba");
+    }
+
+    #[test]
+    fn test_merge_all_is_stable() {
+        let streams = || vec![tagged(&[0, 1], 'a'), tagged(&[1], 'b'), tagged(&[0, 1], 'c')];
+        assert!(tags(Stream::merge_all(streams())) == "acabc");
+        let reversed = streams().into_iter().rev();
+        assert!(tags(Stream::merge_all(reversed)) == "cacba");
+    }
+
+    #[test]
+    fn test_deep_merge() {
+        let mut actual = Stream::range(0, 3);
+        for i in 0..300000 {
+            actual = match i % 2 {
+                0 => actual.merge(&Stream::empty()),
+                _ => Stream::merge_all(vec![actual]),
+            };
+        }
+        assert!(is_match_with_vec(actual, vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_iterate() {
+        let actual = Stream::iterate(1, |x| x * 2);
+        assert!(is_match_with_vec(actual.take(5), vec![1, 2, 4, 8, 16]));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let actual = Stream::unfold((0, 1), |(x, y)| Some((x, (y, x + y))));
+        assert!(is_match_with_vec(actual.take(7), vec![0, 1, 1, 2, 3, 5, 8]));
+
+        let actual = Stream::unfold(3, |n| if n == 0 { None } else { Some((n, n - 1)) });
+        assert!(is_match_with_vec(actual, vec![3, 2, 1]));
+    }
+
+    #[test]
+    fn test_repeat() {
+        let actual = Stream::repeat(7);
+        assert!(is_match_with_vec(actual.drop_nth(1000).take(3), vec![7, 7, 7]));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let actual = Stream::empty().cons(&3).cons(&2).cons(&1).cycle();
+        assert!(is_match_with_vec(actual.take(7), vec![1, 2, 3, 1, 2, 3, 1]));
+
+        let actual = Stream::<i32>::empty().cycle();
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn test_range() {
+        assert!(is_match_with_vec(Stream::range(3, 6), vec![3, 4, 5]));
+        assert!(Stream::range(6, 3).is_empty());
+        assert!(is_match_with_vec(Stream::count_from(10u64).drop_nth(5).take(2), vec![15, 16]));
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let pulled = Rc::new(Cell::new(0));
+        let counter = pulled.clone();
+        let actual = Stream::from_iter((0..).inspect(move |_| counter.set(counter.get() + 1)));
+        assert!(pulled.get() == 0);
+
+        assert!(is_match_with_vec(actual.take(3), vec![0, 1, 2]));
+        assert!(pulled.get() == 3);
+
+        let replay = actual.clone();
+        assert!(is_match_with_vec(replay.take(4), vec![0, 1, 2, 3]));
+        assert!(is_match_with_vec(actual.take(4), vec![0, 1, 2, 3]));
+        assert!(pulled.get() == 4);
+
+        assert!(is_match_with_vec(Stream::from_iter(vec![1, 2]), vec![1, 2]));
+    }
+
+    fn counted<'a>(evaluated: &Rc<Cell<usize>>) -> Stream<'a, i32> {
+        let counter = evaluated.clone();
+        Stream::from_iter((0..).inspect(move |_| counter.set(counter.get() + 1)))
+    }
+
+    #[test]
+    fn test_map() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).map(|x| x.to_string());
+        assert!(evaluated.get() == 0);
+        let expected = vec!["0".to_string(), "1".to_string(), "2".to_string()];
+        assert!(is_match_with_vec(actual.take(3), expected));
+        assert!(evaluated.get() == 3);
+    }
+
+    #[test]
+    fn test_filter() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).filter(|x| x % 3 == 0);
+        assert!(is_match_with_vec(actual.take(3), vec![0, 3, 6]));
+        assert!(evaluated.get() == 7);
+    }
+
+    #[test]
+    fn test_scan() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).scan(0, |acc, x| acc + x);
+        assert!(is_match_with_vec(actual.take(5), vec![0, 0, 1, 3, 6]));
+        assert!(evaluated.get() == 4);
+    }
+
+    #[test]
+    fn test_take_while() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).take_while(|x| *x < 3);
+        assert!(is_match_with_vec(actual.clone(), vec![0, 1, 2]));
+        assert!(evaluated.get() == 3);
+        assert!(actual.drop_nth(3).is_empty());
+        assert!(evaluated.get() == 4);
+    }
+
+    #[test]
+    fn test_drop_while() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).drop_while(|x| *x < 3);
+        assert!(evaluated.get() == 0);
+        assert!(actual.head() == Some(&3));
+        assert!(evaluated.get() == 4);
+        assert!(Stream::range(0, 3).drop_while(|x| *x < 5).is_empty());
+    }
+
+    #[test]
+    fn test_flat_map() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).flat_map(|x| Stream::range(0, *x));
+        assert!(is_match_with_vec(actual.take(4), vec![0, 0, 1, 0]));
+        assert!(evaluated.get() == 4);
+    }
+
+    #[test]
+    fn test_interleave() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).interleave(&Stream::range(10, 12));
+        assert!(is_match_with_vec(actual.take(6), vec![0, 10, 1, 11, 2, 3]));
+        assert!(evaluated.get() == 4);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated).chunks(3);
+        assert!(is_match_with_vec(actual.take(2), vec![vec![0, 1, 2], vec![3, 4, 5]]));
+        assert!(evaluated.get() == 6);
+        let actual = Stream::range(0, 5).chunks(2);
+        assert!(is_match_with_vec(actual, vec![vec![0, 1], vec![2, 3], vec![4]]));
+    }
+
+    #[test]
+    fn test_lazy_pipeline() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated)
+            .map(|x| x * x)
+            .filter(|x| x % 2 == 1)
+            .zip_with(&Stream::count_from(1), |x, i| x + i)
+            .take_while(|x| *x < 100);
+        assert!(evaluated.get() == 0);
+        assert!(actual.head() == Some(&2));
+        assert!(evaluated.get() == 2);
+    }
+
+    fn last_and_len<'a>(xs: &Stream<'a, i32>) -> (Option<i32>, usize) {
+        xs.iter().fold((None, 0), |(_, len), x| (Some(x), len + 1))
+    }
+
+    #[test]
+    fn test_iter() {
+        let actual = Stream::empty().cons(&3).cons(&2).cons(&1);
+        assert!(actual.iter().collect::<Vec<_>>() == vec![1, 2, 3]);
+        assert!(actual.iter().map(|x| x * 10).sum::<i32>() == 60);
+
+        let mut expected = 1;
+        for x in &actual {
+            assert!(x == expected);
+            expected += 1;
+        }
+        assert!(actual.into_iter().count() == 3);
+    }
+
+    #[test]
+    fn test_iter_forces_prefix() {
+        let evaluated = Rc::new(Cell::new(0));
+        let actual = counted(&evaluated);
+        let mut iter = actual.iter();
+        assert!(evaluated.get() == 0);
+        assert!(iter.next() == Some(0));
+        assert!(evaluated.get() == 1);
+        assert!(iter.take(4).collect::<Vec<_>>() == vec![1, 2, 3, 4]);
+        assert!(evaluated.get() == 5);
+        assert!(actual.iter().position(|x| x == 2) == Some(2));
+        assert!(evaluated.get() == 5);
+    }
+
+    #[test]
+    fn test_deep_concat() {
+        let mut actual = Stream::empty();
+        for i in 0..1000000 {
+            actual = actual.snoc(&i);
+        }
+        assert!(is_match_with_vec(actual.take(3), vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_deep_incremental() {
+        let mut actual = Stream::range(0, 3);
+        for i in 0..1000000 {
+            actual = match i % 5 {
+                0 => actual.map(|x| x + 1),
+                1 => actual.filter(|x| *x >= 0),
+                2 => actual.drop_while(|x| *x < 0),
+                3 => actual.zip_with(&Stream::repeat(0), |x, y| x + y),
+                _ => actual.flat_map(|x| Stream::empty().cons(x)),
+            };
+        }
+        assert!(is_match_with_vec(actual, vec![200000, 200001, 200002]));
+    }
+
+    #[test]
+    fn test_deep_merge_sort() {
+        let mut actual = Stream::empty().cons(&1).cons(&3).cons(&2);
+        for _ in 0..300000 {
+            actual = actual.merge_sort();
+        }
+        assert!(is_match_with_vec(actual, vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_drop_deep_concat() {
+        let mut actual = Stream::empty();
+        for i in 0..1000000 {
+            actual = Stream::empty().cons(&i).concat(&actual);
+        }
+        drop(actual);
+    }
+
+    #[test]
+    fn test_long_concat() {
+        let mut actual = Stream::empty();
+        for i in 0..1000000 {
+            actual = actual.cons(&i);
+        }
+        assert!(last_and_len(&actual) == (Some(0), 1000000));
+    }
+
+    #[test]
+    fn test_long_reverse() {
+        let mut actual = Stream::empty();
+        for i in 0..1000000 {
+            actual = actual.cons(&i);
+        }
+        let actual = actual.reverse();
+        assert!(is_match_with_vec(actual.drop_nth(999998), vec![999998, 999999]));
+        assert!(last_and_len(&actual) == (Some(999999), 1000000));
+    }
+
+    #[test]
+    #[cfg(feature = "cost")]
+    fn test_cost_reverse() {
+        use lazy::cost;
+
+        let n = 1000;
+        let (actual, built) = cost::measure(|| {
+            let mut actual = Stream::empty();
+            for i in 0..n {
+                actual = Stream::empty().cons(&(i as i32)).concat(&actual);
+            }
+            actual
+        });
+        assert!(built.created == n && built.evaluated == 0);
+
+        // Prepending neither suspends nor forces anything.
+        let (_, prepended) = cost::measure(|| actual.cons(&-1));
+        assert!(prepended == cost::Cost::default());
+
+        // Walking the reversal forces the whole input once: linear, not quadratic.
+        let reversed = actual.reverse();
+        let (_, first) = cost::measure(|| last_and_len(&reversed));
+        assert!(first.evaluated <= 6 * n);
+
+        // Both the new and the old version are memoized; walking them again evaluates nothing.
+        let (_, again) = cost::measure(|| last_and_len(&reversed));
+        assert!(again.evaluated == 0 && again.created == 0);
+        let (_, old) = cost::measure(|| last_and_len(&actual));
+        assert!(old.evaluated == 0 && old.created == 0);
     }
 
     fn is_match_with_vec<'a, T>(xs: Stream<'a, T>, ys: Vec<T>) -> bool
         where T: 'a + Debug + PartialEq + PartialOrd + Clone
     {
-        ys.iter()
-            .fold((xs, true), |(xs, prev), y| match *xs {
-                Nil => (susp!(Nil), false),
-                Cons(ref head, box ref tail) => (tail.clone(), prev && head == y),
-            })
-            .1
+        let prefix = xs.iter().take(ys.len()).collect::<Vec<_>>();
+        prefix == ys
     }
 }
 
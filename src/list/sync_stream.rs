@@ -0,0 +1,247 @@
+use std::fmt::Debug;
+use lazy::SyncSusp;
+use list::unlink;
+
+use self::SyncStreamCell::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SyncStreamCell<T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync> {
+    Nil,
+    Cons(T, SyncStream<T>),
+}
+
+impl<T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync> SyncStreamCell<T> {
+    fn drop_impl(&self, n: i32) -> Self {
+        let mut cell = self.clone();
+        for _ in 0..n {
+            cell = match cell {
+                Nil => return Nil,
+                Cons(_, ref tail) => tail.force().clone(),
+            };
+        }
+        cell
+    }
+
+    fn reverse_impl(&self, other: &Self) -> Self {
+        let mut reversed = other.clone();
+        let mut cell = self.clone();
+        loop {
+            cell = match cell {
+                Nil => return reversed,
+                Cons(ref head, ref tail) => {
+                    reversed = Cons(head.clone(), SyncStream(SyncSusp::from_value(reversed)));
+                    tail.force().clone()
+                }
+            };
+        }
+    }
+}
+
+impl<T> Drop for SyncStreamCell<T>
+    where T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync
+{
+    fn drop(&mut self) {
+        unlink(self, |cell| match *cell {
+                   Nil => None,
+                   Cons(_, ref mut tail) => tail.0.take_unique(),
+               });
+    }
+}
+
+// `Stream` for sharing between threads: each cell is evaluated once, by whichever thread
+// forces it first.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyncStream<T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync>(
+    SyncSusp<SyncStreamCell<T>>
+);
+
+impl<T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync> SyncStream<T> {
+    pub fn empty() -> Self {
+        SyncStream(SyncSusp::from_value(Nil))
+    }
+
+    // Suspends the computation of the first cell.
+    pub fn lazy<F>(f: F) -> Self where F: 'static + Send + FnOnce() -> SyncStreamCell<T> {
+        SyncStream(SyncSusp::new(f))
+    }
+
+    pub fn iter(&self) -> Iter<T> {
+        Iter { rest: self.clone() }
+    }
+
+    pub fn force(&self) -> &SyncStreamCell<T> {
+        &*self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self.force() {
+            &Nil => true,
+            &Cons(_, _) => false,
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        match self.force() {
+            &Nil => None,
+            &Cons(ref head, _) => Some(head),
+        }
+    }
+
+    pub fn tail(&self) -> Option<Self> {
+        match self.force() {
+            &Nil => None,
+            &Cons(_, ref tail) => Some(tail.clone()),
+        }
+    }
+
+    // O(1): the new cell is already evaluated and the stream itself is not forced.
+    pub fn cons(&self, x: &T) -> Self {
+        SyncStream(SyncSusp::from_value(Cons(x.clone(), self.clone())))
+    }
+
+    pub fn snoc(&self, x: &T) -> Self {
+        self.concat(&SyncStream::empty().cons(x))
+    }
+
+    pub fn concat(&self, other: &Self) -> Self {
+        let this = self.clone();
+        let that = other.clone();
+        SyncStream::lazy(move || match this.force() {
+                             &Nil => that.force().clone(),
+                             &Cons(ref head, ref tail) => Cons(head.clone(), tail.concat(&that)),
+                         })
+    }
+
+    pub fn take(&self, n: i32) -> Self {
+        let this = self.clone();
+        SyncStream::lazy(move || match this.force() {
+                             &Nil => Nil,
+                             _ if n == 0 => Nil,
+                             &Cons(ref head, ref tail) => Cons(head.clone(), tail.take(n - 1)),
+                         })
+    }
+
+    pub fn drop_nth(&self, n: i32) -> Self {
+        let this = self.clone();
+        SyncStream::lazy(move || this.force().drop_impl(n))
+    }
+
+    pub fn reverse(&self) -> Self {
+        let this = self.clone();
+        SyncStream::lazy(move || this.force().reverse_impl(&Nil))
+    }
+}
+
+pub struct Iter<T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync> {
+    rest: SyncStream<T>,
+}
+
+impl<T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (x, rest) = match self.rest.force() {
+            &Nil => return None,
+            &Cons(ref x, ref xs) => (x.clone(), xs.clone()),
+        };
+        self.rest = rest;
+        Some(x)
+    }
+}
+
+impl<T> IntoIterator for SyncStream<T>
+    where T: 'static + Debug + PartialEq + PartialOrd + Clone + Send + Sync
+{
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Iter<T> {
+        Iter { rest: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    fn counted(from: i32, to: i32, count: Arc<AtomicUsize>) -> SyncStream<i32> {
+        SyncStream::lazy(move || {
+                             count.fetch_add(1, Ordering::SeqCst);
+                             if from == to {
+                                 Nil
+                             } else {
+                                 Cons(from, counted(from + 1, to, count.clone()))
+                             }
+                         })
+    }
+
+    fn is_send_sync<T: Send + Sync>(_: &T) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_send_sync() {
+        assert!(is_send_sync(&SyncStream::empty().cons(&1)));
+    }
+
+    #[test]
+    fn test_shared_between_workers() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let stream = counted(0, 100, count.clone());
+
+        let workers = (0..4)
+            .map(|_| {
+                     let stream = stream.clone();
+                     thread::spawn(move || stream.iter().sum::<i32>())
+                 })
+            .collect::<Vec<_>>();
+
+        for worker in workers {
+            assert!(worker.join().unwrap() == 4950);
+        }
+        assert!(count.load(Ordering::SeqCst) == 101);
+    }
+
+    #[test]
+    fn test_head_tail() {
+        let actual = SyncStream::empty().cons(&2).cons(&1);
+        assert!(actual.head() == Some(&1));
+        assert!(actual.tail().unwrap().head() == Some(&2));
+        assert!(actual.tail().unwrap().tail().unwrap().is_empty());
+        assert!(SyncStream::<i32>::empty().tail().is_none());
+    }
+
+    #[test]
+    fn test_concat() {
+        let actual_1 = SyncStream::empty().cons(&2).cons(&1);
+        let actual_2 = SyncStream::empty().snoc(&3);
+        let actual = actual_1.concat(&actual_2);
+        assert!(actual.iter().collect::<Vec<_>>() == vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_take_drop() {
+        let actual = SyncStream::empty().cons(&1).cons(&2).cons(&3);
+        assert!(actual.take(2).iter().collect::<Vec<_>>() == vec![3, 2]);
+        assert!(actual.drop_nth(2).iter().collect::<Vec<_>>() == vec![1]);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let actual = SyncStream::empty().snoc(&1).snoc(&2).snoc(&3).reverse();
+        assert!(actual.iter().collect::<Vec<_>>() == vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_long() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let actual = counted(0, 1000000, count.clone());
+        assert!(actual.drop_nth(999999).head() == Some(&999999));
+        assert!(actual.reverse().head() == Some(&999999));
+        drop(actual);
+        assert!(count.load(Ordering::SeqCst) == 1000001);
+    }
+}
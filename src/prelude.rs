@@ -0,0 +1,28 @@
+pub use list::{List, Queue, Deque};
+pub use list::stack::Stack;
+pub use list::stream::Stream;
+pub use list::sync_stream::SyncStream;
+pub use list::linked_list::LinkedList;
+pub use list::queue::BatchedQueue;
+pub use list::bankers_queue::BankersQueue;
+pub use list::physicists_queue::PhysicistsQueue;
+pub use list::real_time_queue::RealTimeQueue;
+pub use list::hood_melville_queue::HoodMelvilleQueue;
+pub use list::deque::BatchedDeque;
+pub use list::bankers_deque::BankersDeque;
+pub use list::real_time_deque::RealTimeDeque;
+
+pub use heap::Heap;
+pub use heap::leftist::LeftistHeap;
+pub use heap::binominal::BinominalHeap;
+pub use heap::explicit_min::ExplicitMin;
+pub use heap::splay::SplayHeap;
+
+pub use set::{Set, Sequence, FiniteMap};
+pub use set::tree::UnBalancedTree;
+pub use set::red_black::RedBlackTree;
+pub use set::map::FiniteMapImpl;
+
+pub use lazy::{Susp, SyncSusp};
+
+pub use error::PfdsError;
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use set::FiniteMap;
 
 #[derive(Debug, Clone, PartialEq)]
-struct FiniteMapImpl<K: Eq + Hash + Clone, V: Clone> {
+pub struct FiniteMapImpl<K: Eq + Hash + Clone, V: Clone> {
     table: HashMap<K, V>,
 }
 
@@ -1,21 +1,21 @@
 use std::fmt::Debug;
 
-mod tree;
-mod red_black;
-mod map;
+pub mod tree;
+pub mod red_black;
+pub mod map;
 
-trait Set<T: Ord + Clone + Debug> {
+pub trait Set<T: Ord + Clone + Debug> {
     fn empty() -> Self;
     fn member(&self, x: &T) -> bool;
     fn insert(&self, x: T) -> Self;
 }
 
-trait Sequence {
+pub trait Sequence {
     fn to_successor_with(&self, skip: i32) -> Self;
     fn to_predecessor_with(&self, skip: i32) -> Self;
 }
 
-trait FiniteMap<K, V> {
+pub trait FiniteMap<K, V> {
     fn empty() -> Self;
     fn bind(&self, key: K, value: V) -> Self;
     fn lookup(&self, key: K) -> Option<V>;
@@ -8,20 +8,24 @@ enum Color {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-enum RedBlackTree<T: Ord + Clone + Debug> {
+enum Tree<T: Ord + Clone + Debug> {
     Leaf,
     Node {
         color: Color,
         element: T,
-        left: Box<RedBlackTree<T>>,
-        right: Box<RedBlackTree<T>>,
+        left: Box<Tree<T>>,
+        right: Box<Tree<T>>,
     },
 }
 
-use self::RedBlackTree::*;
+// The tree behind a private field, so that its colors and shape stay free to change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedBlackTree<T: Ord + Clone + Debug>(Tree<T>);
+
+use self::Tree::*;
 use self::Color::*;
 
-impl<T: Ord + Clone + Debug> RedBlackTree<T> {
+impl<T: Ord + Clone + Debug> Tree<T> {
     fn balance_left(color: &Color, element: &T, left: &Self, right: &Self) -> Self {
         match (color, element.clone(), left.clone()) {
             (&Black,
@@ -130,9 +134,9 @@ impl<T: Ord + Clone + Debug> RedBlackTree<T> {
             }
             &Node { ref color, ref element, box ref left, box ref right } => {
                 if x < element {
-                    RedBlackTree::balance_left(color, element, &left.insert_inner(x), right)
+                    Tree::balance_left(color, element, &left.insert_inner(x), right)
                 } else if x > element {
-                    RedBlackTree::balance_right(color, element, left, &right.insert_inner(x))
+                    Tree::balance_right(color, element, left, &right.insert_inner(x))
                 } else {
                     self.clone()
                 }
@@ -140,16 +144,6 @@ impl<T: Ord + Clone + Debug> RedBlackTree<T> {
         }
     }
 
-    fn from_ordered_list(xs: Vec<T>) -> Self {
-        xs.iter().fold(RedBlackTree::empty(), |acc, x| acc.insert(x.clone()))
-    }
-}
-
-impl<T: Ord + Clone + Debug> Set<T> for RedBlackTree<T> {
-    fn empty() -> Self {
-        RedBlackTree::Leaf
-    }
-
     fn member(&self, x: &T) -> bool {
         match self {
             &Leaf => false,
@@ -180,10 +174,30 @@ impl<T: Ord + Clone + Debug> Set<T> for RedBlackTree<T> {
     }
 }
 
+impl<T: Ord + Clone + Debug> RedBlackTree<T> {
+    pub fn from_ordered_list(xs: Vec<T>) -> Self {
+        xs.iter().fold(RedBlackTree::empty(), |acc, x| acc.insert(x.clone()))
+    }
+}
+
+impl<T: Ord + Clone + Debug> Set<T> for RedBlackTree<T> {
+    fn empty() -> Self {
+        RedBlackTree(Leaf)
+    }
+
+    fn member(&self, x: &T) -> bool {
+        self.0.member(x)
+    }
+
+    fn insert(&self, x: T) -> Self {
+        RedBlackTree(self.0.insert(x))
+    }
+}
+
 mod tests {
     use super::*;
 
-    fn is_red_has_black<T: Ord + Clone + Debug>(this: &RedBlackTree<T>) -> bool {
+    fn is_red_has_black<T: Ord + Clone + Debug>(this: &Tree<T>) -> bool {
         match this {
             &Node { color: Red, left: box Node { color: Red, .. }, .. } |
             &Node { color: Red, right: box Node { color: Red, .. }, .. } => false,
@@ -191,7 +205,7 @@ mod tests {
         }
     }
 
-    fn count_black<T: Ord + Clone + Debug>(this: &RedBlackTree<T>, count: i32) -> Vec<i32> {
+    fn count_black<T: Ord + Clone + Debug>(this: &Tree<T>, count: i32) -> Vec<i32> {
         match this {
             &Leaf => vec![count + 1],
             &Node { ref color, ref left, ref right, .. } => {
@@ -210,7 +224,7 @@ mod tests {
         }
     }
 
-    fn has_same_blacks<T: Ord + Clone + Debug>(this: &RedBlackTree<T>) -> bool {
+    fn has_same_blacks<T: Ord + Clone + Debug>(this: &Tree<T>) -> bool {
         let blacks = count_black(this, 0);
         match blacks.first() {
             Some(count) => blacks.iter().all(|x| x == count),
@@ -222,8 +236,8 @@ mod tests {
     fn test_from_ordered_list() {
         let actual = RedBlackTree::from_ordered_list(vec![1, 2, 3, 4, 5, 6, 7]);
 
-        assert!(is_red_has_black(&actual));
-        assert!(has_same_blacks(&actual));
+        assert!(is_red_has_black(&actual.0));
+        assert!(has_same_blacks(&actual.0));
     }
 
     #[test]
@@ -233,8 +247,8 @@ mod tests {
             .insert(2)
             .insert(3);
 
-        assert!(is_red_has_black(&actual));
-        assert!(has_same_blacks(&actual));
+        assert!(is_red_has_black(&actual.0));
+        assert!(has_same_blacks(&actual.0));
     }
 
     #[test]
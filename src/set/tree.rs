@@ -2,7 +2,7 @@ use std::fmt::Debug;
 use set::{Set, Sequence};
 
 #[derive(PartialEq, Debug, Clone)]
-enum UnBalancedTree<T: Ord + Clone + Debug + Sequence> {
+pub enum UnBalancedTree<T: Ord + Clone + Debug + Sequence> {
     Leaf,
     Node(Box<UnBalancedTree<T>>, T, Box<UnBalancedTree<T>>),
 }
@@ -51,7 +51,7 @@ impl<T: Ord + Clone + Debug + Sequence> UnBalancedTree<T> {
         }
     }
 
-    fn complete(x: T, d: i32) -> Self {
+    pub fn complete(x: T, d: i32) -> Self {
         match d {
             1 => UnBalancedTree::empty().insert(x),
             _ => {
@@ -62,7 +62,7 @@ impl<T: Ord + Clone + Debug + Sequence> UnBalancedTree<T> {
         }
     }
 
-    fn create(x: T, d: i32) -> Self {
+    pub fn create(x: T, d: i32) -> Self {
         match d {
             1 => UnBalancedTree::empty().insert(x),
             _ => {